@@ -1,7 +1,10 @@
-use anyhow::Result;
-use serde::Deserialize;
+use anyhow::{bail, Result};
+use colored::{Color, Colorize};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
 
+use crate::domain::{validate_prio_levels, Column, GroupBy, PrioLevel, StatusFilter};
 use crate::infrastructure;
 use crate::infrastructure::UserPaths;
 
@@ -10,6 +13,15 @@ pub struct Config {
     pub database: Database,
     pub style: Style,
     pub aliases: Option<HashMap<String, String>>,
+    pub templates: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default = "PrioLevel::defaults")]
+    pub priorities: Vec<PrioLevel>,
+    #[serde(default)]
+    pub board: BoardConfig,
+    #[serde(default)]
+    pub views: HashMap<String, ViewConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,24 +51,143 @@ impl From<String> for TableStyle {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum IdMode {
+    #[default]
+    Id,
+    Number,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Style {
     pub id_length: usize,
+    #[serde(default)]
+    pub id_mode: IdMode,
     pub due_date_display_format: String,
     pub due_date_input_format: String,
     pub show_due: bool,
     pub show_tag: bool,
     pub sort_by: String,
     pub table: String,
+    pub columns: Option<Vec<Column>>,
+    pub group_by: Option<GroupBy>,
+    #[serde(default = "default_due_horizon")]
+    pub due_horizon: i64,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct BoardConfig {
+    pub group_by: Option<GroupBy>,
+    pub columns: Vec<String>,
+    pub limit: Option<usize>,
+    pub wip: HashMap<String, usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ViewConfig {
+    #[serde(rename = "where")]
+    pub filter: Option<String>,
+    pub status: Option<StatusFilter>,
+    pub sort: Option<String>,
+    pub group_by: Option<GroupBy>,
+    pub columns: Option<Vec<Column>>,
+    pub format: Option<String>,
+}
+
+impl ViewConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == ViewConfig::default()
+    }
+}
+
+fn default_due_horizon() -> i64 {
+    7
+}
+
+// Unknown keys fail loudly, the prio colors that used to live here moved to [[priorities]].
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub id: String,
+    pub tag: String,
+    pub done: String,
+    pub in_progress: String,
+    pub blocked: String,
+    pub cancelled: String,
+    pub overdue: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            id: "yellow".to_string(),
+            tag: String::new(),
+            done: "green".to_string(),
+            in_progress: "cyan".to_string(),
+            blocked: "magenta".to_string(),
+            cancelled: "bright black".to_string(),
+            overdue: "red".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    fn validate(&self) -> Result<()> {
+        let colors = [
+            ("id", &self.id),
+            ("tag", &self.tag),
+            ("done", &self.done),
+            ("in_progress", &self.in_progress),
+            ("blocked", &self.blocked),
+            ("cancelled", &self.cancelled),
+            ("overdue", &self.overdue),
+        ];
+        for (key, color) in colors {
+            validate_color(color, &format!("theme.{key}"))?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_color(color: &str, key: &str) -> Result<()> {
+    if !color.is_empty() && Color::from_str(color).is_err() {
+        bail!("✘ Unknown color '{color}' for {key}");
+    }
+    Ok(())
+}
+
+fn validate_priorities(levels: &[PrioLevel]) -> Result<()> {
+    validate_prio_levels(levels)?;
+    for level in levels {
+        validate_color(&level.color, &format!("priority '{}'", level.name))?;
+    }
+    Ok(())
+}
+
+pub fn paint(text: &str, color: &str) -> String {
+    match Color::from_str(color) {
+        Ok(color) => text.color(color).to_string(),
+        _ => text.to_string(),
+    }
 }
 
 pub fn load_config() -> Result<Config> {
     let paths = UserPaths::new();
     let config = infrastructure::config::read_config(&paths)?;
+    config.theme.validate()?;
+    validate_priorities(&config.priorities)?;
 
     Ok(Config {
         database: config.database,
         style: config.style,
         aliases: config.aliases,
+        templates: config.templates,
+        theme: config.theme,
+        priorities: config.priorities,
+        board: config.board,
+        views: config.views,
     })
 }
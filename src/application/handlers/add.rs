@@ -1,5 +1,5 @@
 use anyhow::Result;
-use uuid::Uuid;
+use ulid::Ulid;
 
 use crate::application::editor::Editor;
 use crate::domain::{add_item::AddOptions, Datetime, Status, TodoItem, TodoItemCreate, TodoList};
@@ -10,7 +10,7 @@ where
 {
     // logging
     match args.due.as_ref() {
-        Some(date) => log::info!("found due date '{}'", date),
+        Some(date) => log::info!("found due date '{}'", date.timestamp),
         None => log::info!("found due date 'None'"),
     };
     let msg = if let Some(task) = args.task {
@@ -21,7 +21,7 @@ where
     log::info!("found task '{}'", msg);
     let due = args.due.unwrap_or(Datetime::epoch());
     let item = TodoItem {
-        id: Uuid::new_v4().to_string(),
+        id: Ulid::new().to_string(),
         task: msg,
         due,
         status: Status::Open,
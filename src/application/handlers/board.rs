@@ -0,0 +1,69 @@
+use anyhow::Result;
+use colored::control::SHOULD_COLORIZE;
+
+use crate::application::config::Config;
+use crate::application::handlers::sort_tasks;
+use crate::application::output::Print;
+use crate::domain::{
+    group_entries, Board, FormatContext, GroupBy, ListFilters, OutputFormat, StatusFilter,
+    TodoItemMetadata, TodoItemQueryColumns, TodoList,
+};
+use crate::util;
+
+pub struct BoardOptions {
+    pub group_by: Option<GroupBy>,
+    pub limit: Option<usize>,
+    pub status: Option<StatusFilter>,
+}
+
+pub fn board<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    config: &Config,
+    options: BoardOptions,
+    output: OutputFormat,
+) -> Result<()>
+where
+    R: TodoItemMetadata + TodoItemQueryColumns,
+{
+    let group_by = options
+        .group_by
+        .or(config.board.group_by)
+        .unwrap_or(GroupBy::Status);
+    // a status board shows every state, other boards only what is left to do
+    let status = options.status.unwrap_or(match group_by {
+        GroupBy::Status => StatusFilter::All,
+        _ => StatusFilter::Do,
+    });
+    let filters = ListFilters {
+        status: Some(status),
+        ..ListFilters::default()
+    };
+    let mut entries = todo_list.get_list_with_metadata(repo, filters)?;
+    sort_tasks(&mut entries, None, config)?;
+    if output.is_table() {
+        let color = SHOULD_COLORIZE.should_colorize();
+        for (todo, _) in entries.iter_mut() {
+            todo.task = util::prettify(&todo.task, color);
+        }
+    }
+    let ids = todo_list.get_ids(repo)?;
+    let ctx = FormatContext::from_config(config).with_ids(&ids);
+    let groups = group_entries(entries, group_by, &ctx);
+    let board = Board::new(groups, &config.board.columns, &config.board.wip);
+    if output.is_table() {
+        let limit = options.limit.or(config.board.limit);
+        println!("{}", board.table(&ctx, config, limit));
+    } else {
+        output.print_records(&board.cards(&config.priorities))?;
+    }
+    for lane in board.lanes.iter().filter(|lane| lane.is_over_limit()) {
+        output.notify(format!(
+            "⚠ '{}' has {} tasks, over its WIP limit of {}",
+            lane.title,
+            lane.cards.len(),
+            lane.wip_limit.unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
@@ -1,13 +1,14 @@
 use anyhow::Result;
 
-use crate::domain::{TodoItemDelete, TodoList};
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, TodoItemDelete, TodoList};
 
-pub fn delete_all<R>(repo: &R, todo_list: &mut TodoList) -> Result<()>
+pub fn delete_all<R>(repo: &R, todo_list: &mut TodoList, output: OutputFormat) -> Result<()>
 where
     R: TodoItemDelete,
 {
-    println!("▶ Deleteing all todos...");
+    output.notify("▶ Deleteing all todos...");
     todo_list.delete_all_items(repo)?;
-    println!("✔ Done");
+    output.notify("✔ Done");
     Ok(())
 }
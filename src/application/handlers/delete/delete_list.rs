@@ -1,44 +1,28 @@
 use anyhow::{anyhow, Result};
-use std::fs;
-use std::io::Write;
 
-use crate::domain::{TodoList, TodoListDelete};
-use crate::infrastructure::{env, UserPaths};
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, SelectionRepository, TodoList, TodoListDelete};
 
-pub fn delete_list<R>(repo: &R, todo_list: &TodoList, list: String) -> Result<()>
+pub fn delete_list<R, S>(
+    repo: &R,
+    state_repo: &S,
+    todo_list: &TodoList,
+    list: String,
+    output: OutputFormat,
+) -> Result<()>
 where
     R: TodoListDelete,
+    S: SelectionRepository,
 {
-    let user_paths = UserPaths::new();
-    let dotenv = env::dotenv(&user_paths);
-    let content = fs::read_to_string(&dotenv)?;
-    log::debug!("reading env {:?}", dotenv);
-    let mut new_content = String::new();
-    for line in content.lines() {
-        if line.starts_with("CURRENT=") {
-            let current_list = line.split('=').next_back().unwrap_or("");
-            if list == current_list {
-                return Err(anyhow!(
-                    "✘ Can't delete the list '{list}' since currently in use"
-                ));
-            };
-            new_content.push_str(&format!("{line}\n"));
-        } else if line.starts_with("PREVIOUS=") {
-            let current_list = line.split('=').next_back().unwrap_or("");
-            if list == current_list {
-                new_content.push_str("PREVIOUS=\n");
-            } else {
-                new_content.push_str(&format!("{line}\n"));
-            };
-        } else {
-            new_content.push_str(&format!("{line}\n"));
-        }
+    let selection = todo_list.get_selection(state_repo)?;
+    if selection.current.as_deref() == Some(list.as_str()) {
+        return Err(anyhow!(
+            "✘ Can't delete the list '{list}' since currently in use"
+        ));
     }
-    println!("▶ Removing list '{list}'...");
+    output.notify(format!("▶ Removing list '{list}'..."));
     todo_list.delete_list(repo, &list)?;
-    println!("✔ Done");
-    log::debug!("writing dotenv `{new_content}`");
-    let mut file = fs::File::create(dotenv)?;
-    file.write_all(new_content.as_bytes())?;
+    todo_list.deselect_list(state_repo, &list)?;
+    output.notify("✔ Done");
     Ok(())
 }
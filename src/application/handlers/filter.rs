@@ -0,0 +1,59 @@
+use anyhow::{bail, Context, Result};
+use chrono::Local;
+
+use crate::application::config::Config;
+use crate::application::output::Print;
+use crate::domain::{
+    FilterContext, FilterExpr, ListFilters, OutputFormat, TodoItemDelete, TodoItemRead, TodoList,
+};
+
+pub fn parse_where(expr: &str, config: &Config) -> Result<FilterExpr> {
+    let ctx = FilterContext {
+        today: Local::now().date_naive(),
+        date_input_format: &config.style.due_date_input_format,
+        levels: &config.priorities,
+    };
+    FilterExpr::parse(expr, &ctx).context("✘ Invalid --where expression")
+}
+
+pub fn matching_ids<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    config: &Config,
+    expr: &str,
+) -> Result<Vec<String>>
+where
+    R: TodoItemRead,
+{
+    let filters = ListFilters {
+        expr: Some(parse_where(expr, config)?),
+        ..ListFilters::default()
+    };
+    let ids: Vec<String> = todo_list
+        .get_list(repo, filters)?
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    if ids.is_empty() {
+        bail!("✘ No task matches '{expr}'");
+    }
+    Ok(ids)
+}
+
+pub fn delete_matching<R>(
+    repo: &R,
+    todo_list: &mut TodoList,
+    config: &Config,
+    expr: &str,
+    output: OutputFormat,
+) -> Result<()>
+where
+    R: TodoItemRead + TodoItemDelete,
+{
+    let ids = matching_ids(repo, todo_list, config, expr)?;
+    for id in ids.iter() {
+        todo_list.delete_item(repo, id)?;
+    }
+    output.notify(format!("✔ Deleted {} tasks", ids.len()));
+    Ok(())
+}
@@ -1,25 +1,29 @@
 use anyhow::Result;
 
-use crate::domain::{grep::GrepOptions, TodoItemRead, TodoList, TodoListTable};
+use crate::application::handlers::{print_list, query_lists};
+use crate::application::output::RenderOptions;
+use crate::domain::{grep::GrepOptions, TodoItemMetadata, TodoItemQueryColumns, TodoList};
 
 use crate::application::config::Config;
 
 pub fn grep<R>(
-    repo: &R,
+    repos: &[R],
     todo_list: &TodoList,
     config: &Config,
     pattern: &str,
     options: GrepOptions,
+    render: &RenderOptions,
 ) -> Result<()>
 where
-    R: TodoItemRead,
+    R: TodoItemMetadata + TodoItemQueryColumns,
 {
-    let matches = todo_list.grep(repo, pattern, options)?;
-    if matches.is_empty() {
+    let (mut matches, ids) = query_lists(repos, todo_list, |repo| {
+        todo_list.grep_with_metadata(repo, pattern, options.clone())
+    })?;
+    if matches.is_empty() && render.output.is_table() {
         println!("ℹ No match found");
+        Ok(())
     } else {
-        let table = TodoListTable::new(&matches, config);
-        table.print();
+        print_list(&mut matches, &ids, config, render)
     }
-    Ok(())
 }
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io::Read;
+use std::path::Path;
+use std::{fs, io};
+use ulid::Ulid;
+
+use crate::application::output::Print;
+use crate::domain::{
+    import_items::ExternalItem, Datetime, OutputFormat, Status, Tag, TodoItemCreate,
+    TodoItemExternalRef, TodoItemUpdate, TodoList, Transactional,
+};
+
+// Matches `gh issue list --json number,title,body,labels,milestone,url`
+// as well as `glab issue list --output json`.
+#[derive(Deserialize, Debug)]
+struct Issue {
+    #[serde(alias = "iid")]
+    number: u64,
+    title: String,
+    #[serde(default, alias = "description")]
+    body: Option<String>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    milestone: Option<Milestone>,
+    #[serde(alias = "web_url")]
+    url: String,
+    #[serde(default)]
+    state: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Label {
+    Named { name: String },
+    Plain(String),
+}
+
+#[derive(Deserialize, Debug)]
+struct Milestone {
+    #[serde(default, rename = "dueOn", alias = "due_date")]
+    due_on: Option<String>,
+}
+
+pub fn import<R>(repo: &R, todo_list: &TodoList, source: &Path, output: OutputFormat) -> Result<()>
+where
+    R: TodoItemCreate + TodoItemUpdate + TodoItemExternalRef + Transactional,
+{
+    let content = if source.as_os_str() == "-" {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .context("✘ Couldn't read issues from stdin")?;
+        buffer
+    } else {
+        fs::read_to_string(source).context(format!("✘ Couldn't read file {}", source.display()))?
+    };
+    let issues: Vec<Issue> =
+        serde_json::from_str(&content).context("✘ Couldn't parse issues as JSON")?;
+    log::info!("found {} issues", issues.len());
+    let items = issues
+        .into_iter()
+        .map(ExternalItem::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    output.notify(format!("▶ Importing {} issues...", items.len()));
+    let summary =
+        repo.transaction(|| todo_list.import_items(repo, items, || Ulid::new().to_string()))?;
+    output.notify(format!(
+        "✔ Imported {} new and updated {} existing items",
+        summary.created, summary.updated
+    ));
+    Ok(())
+}
+
+impl TryFrom<Issue> for ExternalItem {
+    type Error = anyhow::Error;
+
+    fn try_from(issue: Issue) -> Result<Self> {
+        // tags are matched as a whole, so the first label becomes the tag and the others
+        // are kept in the task
+        let mut labels = issue.labels.iter().map(|label| match label {
+            Label::Named { name } | Label::Plain(name) => name.replace(' ', "-"),
+        });
+        let tag = Tag(labels.next().unwrap_or_default());
+        let others = labels.collect::<Vec<_>>();
+        let mut parts = vec![issue.title];
+        if let Some(body) = issue
+            .body
+            .as_deref()
+            .map(str::trim)
+            .filter(|body| !body.is_empty())
+        {
+            parts.push(body.to_string());
+        }
+        if !others.is_empty() {
+            parts.push(format!("Labels: {}", others.join(", ")));
+        }
+        parts.push(format!("[#{}]({})", issue.number, issue.url));
+        let task = parts.join("\n\n");
+        let due = match issue.milestone.and_then(|milestone| milestone.due_on) {
+            Some(due_on) => {
+                let date = due_on.get(..10).unwrap_or(&due_on);
+                Datetime::parse(date, "%Y-%m-%d".to_string())
+                    .context(format!("✘ Invalid milestone due date on #{}", issue.number))?
+            }
+            None => Datetime::epoch(),
+        };
+        let status = issue
+            .state
+            .map(|state| match state.to_lowercase().as_str() {
+                "closed" => Status::Closed,
+                _ => Status::Open,
+            });
+        Ok(ExternalItem {
+            external_ref: issue.url,
+            task,
+            tag,
+            due,
+            status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_tag_with_the_first_label() -> Result<()> {
+        let issue: Issue = serde_json::from_str(
+            r#"{"number": 7, "title": "Crash", "body": "On start", "url": "https://x/7",
+                "labels": [{"name": "bug"}, {"name": "p1"}, {"name": "needs triage"}]}"#,
+        )?;
+        let item = ExternalItem::try_from(issue)?;
+        assert_eq!(item.tag, Tag("bug".to_string()));
+        assert_eq!(
+            item.task,
+            "Crash\n\nOn start\n\nLabels: p1, needs-triage\n\n[#7](https://x/7)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_leave_tag_empty_without_labels() -> Result<()> {
+        let issue: Issue =
+            serde_json::from_str(r#"{"iid": 7, "title": "Crash", "web_url": "https://x/7"}"#)?;
+        let item = ExternalItem::try_from(issue)?;
+        assert_eq!(item.tag, Tag::default());
+        Ok(())
+    }
+}
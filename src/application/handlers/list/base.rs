@@ -1,68 +1,128 @@
 use anyhow::{Context, Result};
-use std::cmp::Reverse;
+use colored::{control::SHOULD_COLORIZE, Colorize};
+use std::str::FromStr;
 
 use crate::application::config::Config;
+use crate::application::output::{Print, RenderOptions};
 use crate::domain::{
-    Datetime, ListFilters, StatusFilter, TodoItem, TodoItemRead, TodoList, TodoListTable,
+    group_entries, Column, FormatContext, ListFilters, Metadata, SortSpec, StatusFilter,
+    TodoEntryView, TodoItem, TodoItemMetadata, TodoItemQueryColumns, TodoList, TodoListTable,
 };
-use crate::infrastructure::{config, UserPaths};
 use crate::util;
 
 pub fn list<R>(
-    repo: &R,
+    repos: &[R],
     todo_list: &TodoList,
     config: &Config,
     sort: Option<String>,
     filters: ListFilters,
+    render: &RenderOptions,
 ) -> Result<()>
 where
-    R: TodoItemRead,
+    R: TodoItemMetadata + TodoItemQueryColumns,
 {
-    let current_list = std::env::var("CURRENT")?;
-    log::debug!("found current list '{}'", &current_list,);
     let filters_or_default = ListFilters {
-        status: Some(filters.status.unwrap_or(StatusFilter::Do)),
+        status: filters.status_or(StatusFilter::Do),
         prio: filters.prio,
         due: filters.due,
         tag: filters.tag,
+        expr: filters.expr,
     };
-    let mut todos = todo_list.get_list(repo, filters_or_default)?;
-    sort_tasks(&mut todos, sort)?;
-    prettify(&mut todos);
-    let table = TodoListTable::new(&todos, config);
-    table.print();
-    Ok(())
+    let (mut entries, ids) = query_lists(repos, todo_list, |repo| {
+        todo_list.get_list_with_metadata(repo, filters_or_default.clone())
+    })?;
+    sort_tasks(&mut entries, sort, config)?;
+    print_list(&mut entries, &ids, config, render)
+}
+
+type Entries = Vec<(TodoItem, Metadata)>;
+
+// the ids of all lists are merged too, the shortest unique id prefixes depend on them
+pub fn query_lists<R, Q>(
+    repos: &[R],
+    todo_list: &TodoList,
+    query: Q,
+) -> Result<(Entries, Vec<String>)>
+where
+    R: TodoItemQueryColumns,
+    Q: Fn(&R) -> Result<Entries>,
+{
+    let mut entries = Vec::new();
+    let mut ids = Vec::new();
+    for repo in repos {
+        entries.extend(query(repo)?);
+        ids.extend(todo_list.get_ids(repo)?);
+    }
+    Ok((entries, ids))
 }
 
-pub fn sort_tasks(todos: &mut [TodoItem], sort_key: Option<String>) -> Result<()> {
-    let user_paths = UserPaths::new();
-    let mut sort_key_default = config::read_config(&user_paths)
-        .context("✘ Couldn't read config while retrieving the sort key")?
-        .style
-        .sort_by;
-    if sort_key_default.is_empty() {
-        sort_key_default = "id".to_string()
+pub fn print_list(
+    entries: &mut [(TodoItem, Metadata)],
+    ids: &[String],
+    config: &Config,
+    render: &RenderOptions,
+) -> Result<()> {
+    let ctx = FormatContext::from_config(config).with_ids(ids);
+    if let Some(template) = &render.template {
+        for (item, metadata) in entries.iter() {
+            println!("{}", template.render(item, metadata, &ctx));
+        }
+        Ok(())
+    } else if render.output.is_table() {
+        let columns = render.columns.clone().unwrap_or_else(|| {
+            let columns = config.style.columns.clone().unwrap_or(Column::defaults(
+                config.style.id_mode,
+                config.style.show_due,
+                config.style.show_tag,
+            ));
+            match render.show_list {
+                true => Column::with_list(columns),
+                false => columns,
+            }
+        });
+        prettify(entries);
+        match render.group_by.or(config.style.group_by) {
+            Some(group_by) => {
+                let groups = group_entries(entries.to_vec(), group_by, &ctx);
+                for (idx, group) in groups.iter().enumerate() {
+                    if idx > 0 {
+                        println!();
+                    }
+                    println!("{}", group.header().bold());
+                    TodoListTable::new(&group.entries, &ctx, config, &columns).print();
+                }
+            }
+            None => TodoListTable::new(entries, &ctx, config, &columns).print(),
+        }
+        Ok(())
+    } else {
+        let views: Vec<TodoEntryView> = entries
+            .iter()
+            .map(|(item, metadata)| TodoEntryView::new(item, metadata, &config.priorities))
+            .collect();
+        render.output.print_records(&views)
+    }
+}
+
+pub fn sort_tasks(
+    entries: &mut [(TodoItem, Metadata)],
+    sort_key: Option<String>,
+    config: &Config,
+) -> Result<()> {
+    let sort_key_default = match config.style.sort_by.as_str() {
+        "" => "id",
+        sort_by => sort_by,
     };
-    let sort_key = sort_key.as_deref().unwrap_or(&sort_key_default);
+    let sort_key = sort_key.as_deref().unwrap_or(sort_key_default);
     log::debug!("using sort key {sort_key}");
-    match sort_key {
-        "id" => todos.sort_by_key(|entry| Reverse(entry.id.clone())),
-        "prio" => todos.sort_by_key(|entry| entry.prio),
-        "tag" => todos.sort_by_key(|entry| {
-            let key = entry.tag.clone();
-            (key.0.is_empty(), key)
-        }),
-        "due" => todos.sort_by_key(|entry| {
-            let key = entry.due;
-            (key == Datetime::epoch(), key)
-        }),
-        _ => todos.sort_by_key(|entry| Reverse(entry.id.clone())),
-    };
+    let spec = SortSpec::from_str(sort_key).context(format!("✘ Invalid sort '{sort_key}'"))?;
+    spec.sort(entries);
     Ok(())
 }
 
-fn prettify(todos: &mut [TodoItem]) {
-    for todo in todos.iter_mut() {
-        todo.task = util::prettify(&todo.task);
+fn prettify(entries: &mut [(TodoItem, Metadata)]) {
+    let color = SHOULD_COLORIZE.should_colorize();
+    for (todo, _) in entries.iter_mut() {
+        todo.task = util::prettify(&todo.task, color);
     }
 }
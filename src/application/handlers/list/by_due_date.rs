@@ -1,40 +1,39 @@
 use anyhow::{anyhow, Result};
 
 use crate::application::config::Config;
-use crate::application::handlers::sort_tasks;
+use crate::application::handlers::{print_list, query_lists, sort_tasks};
+use crate::application::output::RenderOptions;
 use crate::domain::{
-    Datetime, ListFilters, StatusFilter, TodoItem, TodoItemQuery, TodoList, TodoListTable,
+    Datetime, ListFilters, StatusFilter, TodoItemMetadata, TodoItemQueryColumns, TodoList,
 };
 
 pub fn list_due_date<R>(
-    repo: &R,
+    repos: &[R],
     todo_list: &TodoList,
     config: &Config,
     date_str: String,
     sort: Option<String>,
     filters: ListFilters,
+    render: &RenderOptions,
 ) -> Result<()>
 where
-    R: TodoItemQuery,
+    R: TodoItemMetadata + TodoItemQueryColumns,
 {
-    let epoch_seconds = if let Some(date) = date_str.strip_prefix("@") {
-        Datetime::parse(date, config.style.due_date_input_format.clone())?.timestamp
+    let due = if let Some(date) = date_str.strip_prefix("@") {
+        Datetime::parse(date, config.style.due_date_input_format.clone())?
     } else {
         return Err(anyhow!("✘ Invalid date"));
     };
     let filters_or_default = ListFilters {
-        status: Some(filters.status.unwrap_or(StatusFilter::Do)),
+        status: filters.status_or(StatusFilter::Do),
         prio: filters.prio,
-        due: None,
+        due: Some(due),
         tag: filters.tag,
+        expr: filters.expr,
     };
-    let entries = todo_list.get_entries_by_due_date(repo, epoch_seconds, filters_or_default)?;
-    let mut tasks: Vec<TodoItem> = Vec::new();
-    for entry in entries {
-        tasks.push(entry);
-    }
-    sort_tasks(&mut tasks, sort)?;
-    let table = TodoListTable::new(&tasks, config);
-    table.print();
-    Ok(())
+    let (mut entries, ids) = query_lists(repos, todo_list, |repo| {
+        todo_list.get_list_with_metadata(repo, filters_or_default.clone())
+    })?;
+    sort_tasks(&mut entries, sort, config)?;
+    print_list(&mut entries, &ids, config, render)
 }
@@ -1,37 +1,35 @@
 use anyhow::Result;
 
 use crate::application::config::Config;
-use crate::application::handlers::sort_tasks;
+use crate::application::handlers::{print_list, query_lists, sort_tasks};
+use crate::application::output::RenderOptions;
 use crate::domain::{
-    ListFilters, StatusFilter, Tag, TodoItem, TodoItemQuery, TodoList, TodoListTable,
+    ListFilters, StatusFilter, Tag, TodoItemMetadata, TodoItemQueryColumns, TodoList,
 };
 
 pub fn list_tag<R>(
-    repo: &R,
+    repos: &[R],
     todo_list: &TodoList,
     config: &Config,
     tag: String,
     sort: Option<String>,
     filters: ListFilters,
+    render: &RenderOptions,
 ) -> Result<()>
 where
-    R: TodoItemQuery,
+    R: TodoItemMetadata + TodoItemQueryColumns,
 {
     let clean_tag = tag.strip_prefix('#').unwrap_or(&tag);
     let filters_or_default = ListFilters {
-        status: Some(filters.status.unwrap_or(StatusFilter::Do)),
+        status: filters.status_or(StatusFilter::Do),
         prio: filters.prio,
         due: filters.due,
-        tag: None,
+        tag: Some(Tag(clean_tag.to_string())),
+        expr: filters.expr,
     };
-    let entries =
-        todo_list.get_entries_by_tag(repo, Tag(clean_tag.to_string()), filters_or_default)?;
-    let mut tasks: Vec<TodoItem> = Vec::new();
-    for entry in entries {
-        tasks.push(entry);
-    }
-    sort_tasks(&mut tasks, sort)?;
-    let table = TodoListTable::new(&tasks, config);
-    table.print();
-    Ok(())
+    let (mut entries, ids) = query_lists(repos, todo_list, |repo| {
+        todo_list.get_list_with_metadata(repo, filters_or_default.clone())
+    })?;
+    sort_tasks(&mut entries, sort, config)?;
+    print_list(&mut entries, &ids, config, render)
 }
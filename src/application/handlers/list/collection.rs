@@ -1,15 +1,86 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::str::FromStr;
 
-use crate::domain::{TodoList, TodoListRead};
+use crate::application::config::Config;
+use crate::application::handlers::check_list;
+use crate::application::output::Print;
+use crate::domain::{
+    summary_table, FormatContext, ListFilters, ListSummary, ListSummaryView, OutputFormat,
+    StatusFilter, SummarySort, TodoItemMetadata, TodoList, TodoListRead,
+};
 
-pub fn list_collection<R>(repo: &R, todo_list: &TodoList) -> Result<()>
+pub struct CollectionOptions {
+    pub archived: bool,
+    pub sort: Option<String>,
+}
+
+pub fn list_collection<L, R, F>(
+    list_repo: &L,
+    open_list: F,
+    todo_list: &TodoList,
+    config: &Config,
+    options: CollectionOptions,
+    current: &str,
+    output: OutputFormat,
+) -> Result<()>
 where
-    R: TodoListRead,
+    L: TodoListRead,
+    R: TodoItemMetadata,
+    F: Fn(&str) -> R,
 {
-    let collection = todo_list.get_collection(repo)?;
-    println!("Your collection\n===============");
-    for list in collection.iter() {
-        println!("• {list}");
+    let ctx = FormatContext::from_config(config);
+    let filters = ListFilters {
+        status: Some(StatusFilter::All),
+        ..ListFilters::default()
+    };
+    let mut summaries = todo_list
+        .get_collection_details(list_repo)?
+        .into_iter()
+        .filter(|details| details.archived == options.archived)
+        .map(|details| {
+            let repo = open_list(&details.name);
+            let entries = todo_list.get_list_with_metadata(&repo, filters.clone())?;
+            let is_current = details.name == current;
+            Ok(ListSummary::new(details, &entries, &ctx, is_current))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(sort) = options.sort.as_deref() {
+        SummarySort::from_str(sort)
+            .context(format!("✘ Invalid sort '{sort}'"))?
+            .sort(&mut summaries);
     }
+    if !output.is_table() {
+        let views: Vec<ListSummaryView> = summaries.iter().map(ListSummaryView::from).collect();
+        return output.print_records(&views);
+    }
+    match options.archived {
+        true => println!("Archived lists"),
+        false => println!("Your collection"),
+    }
+    println!("{}", summary_table(&summaries, &ctx, config));
     Ok(())
 }
+
+pub fn lists_in_scope<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    all_lists: bool,
+    lists: Option<Vec<String>>,
+    current: &str,
+) -> Result<Vec<String>>
+where
+    R: TodoListRead,
+{
+    if all_lists {
+        return todo_list.get_active_lists(repo);
+    }
+    match lists {
+        Some(lists) => {
+            for list in lists.iter() {
+                check_list(repo, todo_list, list)?;
+            }
+            Ok(lists)
+        }
+        None => Ok(vec![current.to_string()]),
+    }
+}
@@ -1,31 +1,58 @@
-use crate::domain::{Metadata, Status, TodoItem, TodoItemMetadata, TodoList};
+use crate::application::config::{paint, Config};
+use crate::application::output::Print;
+use crate::domain::{
+    FormatContext, Metadata, OutputFormat, Status, TodoEntryView, TodoItem, TodoItemMetadata,
+    TodoList,
+};
 use anyhow::Result;
-use colored::*;
+use colored::{control::SHOULD_COLORIZE, *};
 
 use crate::util;
 
-pub fn show<R>(repo: &R, todo_list: &TodoList, id: &str) -> Result<()>
+pub fn show<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    config: &Config,
+    id: &str,
+    output: OutputFormat,
+) -> Result<()>
 where
     R: TodoItemMetadata,
 {
     let (item, metadata) = todo_list.get_entry_with_metadata(repo, id)?;
-    pretty_print(item, metadata);
-    Ok(())
+    if output.is_table() {
+        pretty_print(item, metadata, config);
+        Ok(())
+    } else {
+        output.print_record(&TodoEntryView::new(&item, &metadata, &config.priorities))
+    }
 }
 
-fn pretty_print(item: TodoItem, metadata: Metadata) {
+fn pretty_print(item: TodoItem, metadata: Metadata, config: &Config) {
     let status = match item.status {
-        Status::Open => "open",
         Status::Closed => "done",
+        status => status.name(),
     };
     let (title, message) = util::parse_task(&item.task);
-    let title = util::prettify(&title);
-    let message = util::prettify(&message);
+    let color = SHOULD_COLORIZE.should_colorize();
+    let title = util::prettify(&title, color);
+    let message = util::prettify(&message, color);
     println!("Id: {}", item.id);
-    println!("Created at: {}", metadata.created_at);
-    println!("Last updated at: {}", metadata.last_updated);
-    println!("Due by: {}", item.due);
-    println!("Priority: {}", item.prio);
+    println!("Number: {}", metadata.number);
+    let ctx = FormatContext::from_config(config);
+    println!("Created at: {}", ctx.date(&metadata.created_at));
+    println!("Last updated at: {}", ctx.date(&metadata.last_updated));
+    let due = if ctx.is_relative_due(&item.due) {
+        format!("{} ({})", ctx.due(&item.due), ctx.date(&item.due))
+    } else {
+        ctx.due(&item.due)
+    };
+    if item.status.is_active() && ctx.is_overdue(&item.due) {
+        println!("Due by: {}", paint(&due, &config.theme.overdue));
+    } else {
+        println!("Due by: {}", due);
+    }
+    println!("Priority: {}", ctx.prio(item.prio));
     println!("Status: {}", status);
     println!("Tag: {}", item.tag);
     println!("\n{}", title.magenta().bold());
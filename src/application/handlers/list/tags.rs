@@ -1,12 +1,27 @@
 use anyhow::Result;
+use serde::Serialize;
 
-use crate::domain::{TodoItemQueryColumns, TodoList};
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, TodoItemQueryColumns, TodoList};
 
-pub fn list_tags<R>(repo: &R, todo_list: &TodoList) -> Result<()>
+#[derive(Serialize)]
+struct TagView<'a> {
+    tag: &'a str,
+}
+
+pub fn list_tags<R>(repo: &R, todo_list: &TodoList, output: OutputFormat) -> Result<()>
 where
     R: TodoItemQueryColumns,
 {
     let tags = todo_list.get_tags(repo)?;
+    if !output.is_table() {
+        let views: Vec<TagView> = tags
+            .iter()
+            .filter(|tag| !tag.0.is_empty())
+            .map(|tag| TagView { tag: &tag.0 })
+            .collect();
+        return output.print_records(&views);
+    }
     println!("Your tags\n==========");
     for tag in tags.iter() {
         println!("• {tag}");
@@ -1,42 +1,53 @@
-use anyhow::{anyhow, Context, Result};
-use std::fs;
-use std::io::Write;
+use anyhow::{anyhow, Result};
 
-use crate::domain::{TodoList, TodoListRead};
-use crate::infrastructure::{env, UserPaths};
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, SelectionRepository, TodoList, TodoListRead};
 
-pub fn load<R>(repo: &R, todo_list: &mut TodoList, list: &str) -> Result<()>
+pub fn load<L, S>(
+    list_repo: &L,
+    state_repo: &S,
+    todo_list: &TodoList,
+    list: &str,
+    output: OutputFormat,
+) -> Result<()>
 where
-    R: TodoListRead,
+    L: TodoListRead,
+    S: SelectionRepository,
 {
-    let user_paths = UserPaths::new();
-    let collection = todo_list.get_collection(repo)?;
+    check_list(list_repo, todo_list, list)?;
+    todo_list.select_list(state_repo, list)?;
+    output.notify(format!("✔ Checked out '{list}'"));
+    Ok(())
+}
+
+pub fn check_list<L>(list_repo: &L, todo_list: &TodoList, list: &str) -> Result<()>
+where
+    L: TodoListRead,
+{
+    let collection = todo_list.get_collection(list_repo)?;
     log::info!("checking if lists exists in collection");
     log::debug!("collection {:?}", &collection);
     if !collection.contains(&list.to_string()) {
-        return Err(anyhow!("✘ Can't find list '{list}'"));
+        return Err(anyhow!(
+            "✘ Can't find list '{list}', see `todo list collection` for all lists"
+        ));
     }
-    let dotenv = env::dotenv(&user_paths);
-    let content = fs::read_to_string(&dotenv).context("✘ Failed to read .env to string")?;
-    log::debug!("dotenv contents: {content}");
-    let mut new_content = String::new();
-    let mut previous = String::from("");
-    log::info!("reading .env");
-    for line in content.lines() {
-        if line.starts_with("CURRENT=") {
-            log::info!("updating PREVIOUS to {previous}");
-            previous.push_str(line.split('=').next_back().unwrap_or(""));
-            log::info!("updating CURRENT to {list}");
-            new_content.push_str(format!("CURRENT={list}\n").as_str());
-        } else if line.starts_with("PREVIOUS=") {
-            new_content.push_str(format!("PREVIOUS={previous}\n").as_str());
-        } else {
-            new_content.push_str(format!("{line}\n").as_str());
-        }
-    }
-    let mut file = fs::File::create(dotenv)?;
-    log::info!("writing back to .env");
-    file.write_all(new_content.as_bytes())?;
-    println!("✔ Checked out '{list}'");
     Ok(())
 }
+
+pub fn load_previous<L, S>(
+    list_repo: &L,
+    state_repo: &S,
+    todo_list: &TodoList,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead,
+    S: SelectionRepository,
+{
+    let previous = todo_list
+        .get_selection(state_repo)?
+        .previous
+        .ok_or(anyhow!("✘ No list was loaded before"))?;
+    load(list_repo, state_repo, todo_list, &previous, output)
+}
@@ -1,25 +1,41 @@
 pub mod add;
+pub mod board;
 pub mod delete;
+pub mod filter;
 pub mod grep;
+pub mod import;
 pub mod list;
 pub mod load;
 pub mod new_list;
 pub mod plumbing;
+pub mod project;
+pub mod resolve;
 pub mod reword;
 pub mod rng;
+pub mod transfer;
+pub mod tui;
 pub mod update;
+pub mod update_list;
 pub mod upgrade;
 pub mod whoami;
 
 pub use add::*;
+pub use board::*;
 pub use delete::*;
+pub use filter::*;
 pub use grep::*;
+pub use import::*;
 pub use list::*;
 pub use load::*;
 pub use new_list::*;
 pub use plumbing::*;
+pub use project::*;
+pub use resolve::*;
 pub use reword::*;
 pub use rng::*;
+pub use transfer::*;
+pub use tui::*;
 pub use update::*;
+pub use update_list::*;
 pub use upgrade::*;
 pub use whoami::*;
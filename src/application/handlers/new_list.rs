@@ -1,20 +1,22 @@
 use anyhow::Result;
 
-use crate::domain::{TodoItemSchema, TodoList, TodoListCreate};
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, TodoItemSchema, TodoList, TodoListCreate, TodoListRead};
 
 pub fn new_list<L, I>(
     todo_list_repo: &L,
     todo_item_repo: &I,
     todo_list: &TodoList,
     list: &str,
+    output: OutputFormat,
 ) -> Result<()>
 where
-    L: TodoListCreate,
+    L: TodoListCreate + TodoListRead,
     I: TodoItemSchema,
 {
-    println!("▶ Creating new list '{list}'...");
+    output.notify(format!("▶ Creating new list '{list}'..."));
     todo_list.add_list(todo_list_repo, list)?;
     todo_list.create_table(todo_item_repo, Some(list))?;
-    println!("✔ Done");
+    output.notify("✔ Done");
     Ok(())
 }
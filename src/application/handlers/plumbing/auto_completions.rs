@@ -1,5 +1,7 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
 use std::fs;
+use std::io::Write;
 
 use clap::CommandFactory;
 use clap_complete::{generate, Shell};
@@ -8,9 +10,13 @@ use crate::cli::app::Cli;
 use crate::infrastructure::config;
 use crate::infrastructure::paths::UserPaths;
 
+// the loaded collection, one list per line
+const LIST_NAMES: &str = "todo list collection -o tsv 2>/dev/null | tail -n +2 | cut -f1";
+// the views of the config, one per line
+const VIEW_NAMES: &str = "todo view list -o tsv 2>/dev/null | tail -n +2 | cut -f1";
+
 pub fn generate_completions(shell: Shell) -> Result<()> {
-    let mut cli_builder = Cli::command();
-    generate(shell, &mut cli_builder, "todo", &mut std::io::stdout());
+    print!("{}", script(shell)?);
     Ok(())
 }
 
@@ -43,9 +49,133 @@ fn install(shell: Shell, dir: std::path::PathBuf, filename: &str) -> Result<()>
     fs::create_dir_all(&dir).context("✘ Couldn't create directory")?;
     let path = dir.join(filename);
     let mut file = fs::File::create(&path).context("✘ Couldn't create file")?;
-    let mut cli_builder = Cli::command();
-    generate(shell, &mut cli_builder, "todo", &mut file);
+    file.write_all(script(shell)?.as_bytes())
+        .context("✘ Couldn't write completions")?;
     println!("✔ Installed {} completions to {}", shell, path.display());
     println!("ℹ Restart your terminal to activate");
     Ok(())
 }
+
+// Only the shells that can run a command while completing get list and view names.
+fn script(shell: Shell) -> Result<String> {
+    let mut cli_builder = Cli::command();
+    let mut buffer = Vec::new();
+    generate(shell, &mut cli_builder, "todo", &mut buffer);
+    let script = String::from_utf8(buffer)?;
+    let script = match shell {
+        Shell::Bash => {
+            // `$$` is a literal `$` in the replacement
+            let script = patch(
+                &script,
+                r#"(?m)^(\s*)(--list|-L|--to)\)\n(\s*)COMPREPLY=\(\$\(compgen -f "\$\{cur\}"\)\)"#,
+                &format!(
+                    "$1$2)\n${{3}}COMPREPLY=($$(compgen -W \"$$({LIST_NAMES})\" -- \"$${{cur}}\"))"
+                ),
+            )?;
+            let script = patch(
+                &script,
+                r#"(?m)^(\s*)--view\)\n(\s*)COMPREPLY=\(\$\(compgen -f "\$\{cur\}"\)\)"#,
+                &format!(
+                    "$1--view)\n${{2}}COMPREPLY=($$(compgen -W \"$$({VIEW_NAMES})\" -- \"$${{cur}}\"))"
+                ),
+            )?;
+            replace(&script, " [VIEW] save", &format!(" $({VIEW_NAMES}) save"))?
+        }
+        Shell::Zsh => {
+            let function = format!(
+                "_todo_lists() {{\n    \
+                     local lists\n    \
+                     lists=(${{(f)\"$({LIST_NAMES})\"}})\n    \
+                     _describe -t lists 'lists' lists\n\
+                 }}\n\n\
+                 _todo_views() {{\n    \
+                     local views\n    \
+                     views=(${{(f)\"$({VIEW_NAMES})\"}})\n    \
+                     _describe -t views 'views' views\n\
+                 }}\n\n"
+            );
+            let script = replace(&script, ":LIST:_default'", ":LIST:_todo_lists'")?;
+            let script = replace(&script, ":VIEW:_default'", ":VIEW:_todo_views'")?;
+            let script = replace(
+                &script,
+                "Name of the view to show:_default'",
+                "Name of the view to show:_todo_views'",
+            )?;
+            replace(
+                &script,
+                "if [ \"$funcstack[1]\" = \"_todo\" ]",
+                &format!("{function}if [ \"$funcstack[1]\" = \"_todo\" ]"),
+            )?
+        }
+        Shell::Fish => {
+            let script = patch(
+                &script,
+                r"(?m)^(complete -c todo .* -l (list|to) .*-r)$",
+                &format!(r#"$1 -f -a "({LIST_NAMES})""#),
+            )?;
+            let script = patch(
+                &script,
+                r"(?m)^(complete -c todo .* -l view .*-r)$",
+                &format!(r#"$1 -f -a "({VIEW_NAMES})""#),
+            )?;
+            patch(
+                &script,
+                r#"(?m)^(complete -c todo -n "__fish_todo_using_subcommand view; [^"]*") -a "save" .*$"#,
+                &format!("$0\n$1 -f -a \"({VIEW_NAMES})\""),
+            )?
+        }
+        _ => script,
+    };
+    Ok(script)
+}
+
+// Both fail rather than ship a script that quietly falls back to completing files once clap
+// changes what it generates.
+fn patch(script: &str, pattern: &str, replacement: &str) -> Result<String> {
+    let re = Regex::new(pattern)?;
+    if !re.is_match(script) {
+        bail!("✘ Couldn't complete names in the generated script, nothing matches '{pattern}'");
+    }
+    Ok(re.replace_all(script, replacement).into_owned())
+}
+
+fn replace(script: &str, from: &str, to: &str) -> Result<String> {
+    if !script.contains(from) {
+        bail!("✘ Couldn't complete names in the generated script, nothing matches '{from}'");
+    }
+    Ok(script.replace(from, to))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_complete_names_in_bash() -> Result<()> {
+        let script = script(Shell::Bash)?;
+        assert!(script.contains(&format!(
+            "--to)\n                    COMPREPLY=($(compgen -W \"$({LIST_NAMES})\""
+        )));
+        assert!(script.contains(&format!("compgen -W \"$({VIEW_NAMES})\"")));
+        assert!(script.contains(&format!(" $({VIEW_NAMES}) save")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_complete_names_in_zsh() -> Result<()> {
+        let script = script(Shell::Zsh)?;
+        assert!(script.contains(LIST_NAMES));
+        assert!(script.contains(VIEW_NAMES));
+        assert!(script.contains(":LIST:_todo_lists'"));
+        assert!(script.contains("Name of the view to show:_todo_views'"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_complete_names_in_fish() -> Result<()> {
+        let script = script(Shell::Fish)?;
+        assert!(script.contains(&format!("-r -f -a \"({LIST_NAMES})\"")));
+        assert!(script.contains(&format!("-f -a \"({VIEW_NAMES})\"")));
+        Ok(())
+    }
+}
@@ -2,8 +2,10 @@ pub mod auto_completions;
 pub mod clean_data;
 pub mod init;
 pub mod show_paths;
+pub mod views;
 
 pub use auto_completions::*;
 pub use clean_data::*;
 pub use init::*;
 pub use show_paths::*;
+pub use views::*;
@@ -1,24 +1,41 @@
+use crate::application::output::Print;
+use crate::domain::OutputFormat;
 use crate::infrastructure::config::{get_todo_config, read_config};
 use crate::infrastructure::paths::UserPaths;
 use anyhow::{anyhow, Result};
+use serde::Serialize;
 
-pub fn show_paths() -> Result<()> {
+#[derive(Serialize)]
+struct PathsView {
+    home: String,
+    config: String,
+    todo_config: String,
+    database: String,
+}
+
+pub fn show_paths(output: OutputFormat) -> Result<()> {
     let user_paths = UserPaths::new();
     let config = read_config(&user_paths)?;
     let db_path = config.database.todo_db;
     let config = get_todo_config(&user_paths)?;
+    let config_dir = user_paths
+        .config
+        .clone()
+        .ok_or(anyhow!(
+            "✘ No standard location for configuration files found"
+        ))?
+        .to_string_lossy()
+        .to_string();
+    if !output.is_table() {
+        return output.print_record(&PathsView {
+            home: user_paths.home.display().to_string(),
+            config: config_dir,
+            todo_config: config.to_string_lossy().to_string(),
+            database: db_path,
+        });
+    }
     println!("{:<16} {}", "home:", user_paths.home.display());
-    println!(
-        "{:<16} {}",
-        "config:",
-        user_paths
-            .config
-            .clone()
-            .ok_or(anyhow!(
-                "✘ No standard location for configuration files found"
-            ))?
-            .to_string_lossy()
-    );
+    println!("{:<16} {}", "config:", config_dir);
     println!("{:<16} {}", "todo.config at:", config.to_string_lossy());
     println!("{:<16} {}", "database at:", db_path);
     Ok(())
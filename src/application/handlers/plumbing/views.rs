@@ -0,0 +1,85 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use tabled::builder::Builder;
+
+use crate::application::config::{Config, ViewConfig};
+use crate::application::output::Print;
+use crate::cli::check_view;
+use crate::domain::table::apply_table_style;
+use crate::domain::OutputFormat;
+use crate::infrastructure::config;
+use crate::infrastructure::paths::UserPaths;
+
+#[derive(Serialize)]
+struct ViewRecord<'a> {
+    name: &'a str,
+    #[serde(flatten)]
+    view: &'a ViewConfig,
+}
+
+pub fn list_views(config: &Config, output: OutputFormat) -> Result<()> {
+    let mut records: Vec<ViewRecord> = config
+        .views
+        .iter()
+        .map(|(name, view)| ViewRecord { name, view })
+        .collect();
+    records.sort_by_key(|record| record.name);
+    if !output.is_table() {
+        return output.print_records(&records);
+    }
+    if records.is_empty() {
+        println!("ℹ No views yet, save one with `todo view save <name>`");
+        return Ok(());
+    }
+    let mut builder = Builder::default();
+    builder.push_record([
+        "view", "where", "status", "sort", "group by", "columns", "format",
+    ]);
+    for record in records {
+        let view = record.view;
+        builder.push_record([
+            record.name.to_string(),
+            view.filter.clone().unwrap_or_default(),
+            cell(&view.status)?,
+            view.sort.clone().unwrap_or_default(),
+            cell(&view.group_by)?,
+            cell(&view.columns)?,
+            view.format.clone().unwrap_or_default(),
+        ]);
+    }
+    let mut table = builder.build();
+    apply_table_style(&mut table, config);
+    println!("{table}");
+    Ok(())
+}
+
+pub fn save_view(
+    name: &str,
+    view: ViewConfig,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<()> {
+    if view.is_empty() {
+        bail!("✘ Nothing to save, pass the flags of the view, e.g. --where or --sort");
+    }
+    check_view(name, &view, config)?;
+    config::save_view(&UserPaths::new(), name, &view)?;
+    output.notify(format!(
+        "✔ Saved view '{name}', use it with `todo view {name}`"
+    ));
+    Ok(())
+}
+
+// the names as written in the config, comma separated for several
+fn cell(value: &impl Serialize) -> Result<String> {
+    Ok(match serde_json::to_value(value)? {
+        Value::String(name) => name,
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+        _ => String::new(),
+    })
+}
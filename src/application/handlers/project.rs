@@ -0,0 +1,40 @@
+use anyhow::{bail, Result};
+
+use crate::application::handlers::new_list;
+use crate::domain::{
+    OutputFormat, TodoItemSchema, TodoList, TodoListCreate, TodoListRead, TodoListSchema,
+};
+use crate::infrastructure::Project;
+
+// A project database is set up on first use, whereas lists of the shared database have to
+// exist already.
+pub fn project_list<L, I>(
+    todo_list_repo: &L,
+    todo_item_repo: &I,
+    todo_list: &TodoList,
+    project: &Project,
+    output: OutputFormat,
+) -> Result<Option<String>>
+where
+    L: TodoListSchema + TodoListCreate + TodoListRead,
+    I: TodoItemSchema,
+{
+    let Some(list) = project.list() else {
+        return Ok(None);
+    };
+    log::info!("using list '{list}' from {:?}", project.path);
+    if project.database().is_some() {
+        todo_list.create_collection(todo_list_repo)?;
+    }
+    let collection = todo_list.get_collection(todo_list_repo)?;
+    if !collection.iter().any(|name| name == list) {
+        if project.database().is_none() {
+            bail!(
+                "✘ Can't find list '{list}' named in {}, create it with `todo new-list {list}`",
+                project.path.display()
+            );
+        }
+        new_list(todo_list_repo, todo_item_repo, todo_list, list, output)?;
+    }
+    Ok(Some(list.to_string()))
+}
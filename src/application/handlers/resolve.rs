@@ -0,0 +1,200 @@
+use anyhow::{bail, Result};
+
+use crate::application::config::{Config, IdMode};
+use crate::application::handlers::sort_tasks;
+use crate::application::picker::Picker;
+use crate::domain::{
+    AmbiguousIdError, FormatContext, ListFilters, Metadata, OutputFormat, StatusFilter, TodoItem,
+    TodoItemMetadata, TodoItemQueryColumns, TodoItemResolve, TodoList, TodoListRead,
+};
+use crate::util::parse_task;
+
+// Without a terminal to pick an ambiguous prefix on, the error lists the candidates instead.
+pub fn resolve_id<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    picker: &impl Picker,
+    config: &Config,
+    id: &str,
+    output: OutputFormat,
+) -> Result<String>
+where
+    R: TodoItemResolve + TodoItemMetadata + TodoItemQueryColumns,
+{
+    let err = match repo.resolve_id(id) {
+        Ok(id) => return Ok(id),
+        Err(err) => err,
+    };
+    let Some(ambiguous) = err.downcast_ref::<AmbiguousIdError>() else {
+        return Err(err);
+    };
+    if !output.is_table() {
+        return Err(err);
+    }
+    let entries = ambiguous
+        .candidates
+        .iter()
+        .map(|item| todo_list.get_entry_with_metadata(repo, &item.id))
+        .collect::<Result<Vec<_>>>()?;
+    let prompt = format!("'{id}' matches {} tasks:", entries.len());
+    match pick(repo, todo_list, picker, config, &prompt, &entries)? {
+        Some(id) => Ok(id),
+        None => Err(err),
+    }
+}
+
+pub fn resolve_ids<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    picker: &impl Picker,
+    config: &Config,
+    ids: Vec<String>,
+    output: OutputFormat,
+) -> Result<Vec<String>>
+where
+    R: TodoItemResolve + TodoItemMetadata + TodoItemQueryColumns,
+{
+    ids.iter()
+        .map(|id| resolve_id(repo, todo_list, picker, config, id, output))
+        .collect()
+}
+
+pub fn pick_id<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    picker: &impl Picker,
+    config: &Config,
+    id: Option<&str>,
+    output: OutputFormat,
+) -> Result<String>
+where
+    R: TodoItemResolve + TodoItemMetadata + TodoItemQueryColumns,
+{
+    if let Some(id) = id {
+        return resolve_id(repo, todo_list, picker, config, id, output);
+    }
+    if !output.is_table() {
+        bail!("✘ Missing task id");
+    }
+    let filters = ListFilters {
+        status: Some(StatusFilter::Do),
+        ..ListFilters::default()
+    };
+    let mut entries = todo_list.get_list_with_metadata(repo, filters)?;
+    if entries.is_empty() {
+        bail!("✘ Missing task id, and there are no open tasks to choose from");
+    }
+    sort_tasks(&mut entries, None, config)?;
+    match pick(repo, todo_list, picker, config, "Select a task:", &entries)? {
+        Some(id) => Ok(id),
+        None => bail!("✘ Missing task id"),
+    }
+}
+
+// Ids that aren't found in `current` are looked up in the other lists, so that a task can be
+// changed without loading its list first, as long as its id matches in one list only.
+pub fn locate_list<L, R, F>(
+    list_repo: &L,
+    open_list: F,
+    todo_list: &TodoList,
+    current: &str,
+    refs: &[String],
+) -> Result<String>
+where
+    L: TodoListRead,
+    R: TodoItemResolve,
+    F: Fn(&str) -> R,
+{
+    let mut located: Option<String> = None;
+    for reference in refs {
+        let list = match reference.split_once(':') {
+            Some((list, _)) => list.to_string(),
+            None => locate_id(list_repo, &open_list, todo_list, current, reference)?,
+        };
+        match &located {
+            Some(other) if *other != list => bail!(
+                "✘ '{reference}' is in list '{list}' and other tasks in '{other}', \
+                change them one list at a time"
+            ),
+            _ => located = Some(list),
+        }
+    }
+    Ok(located.unwrap_or(current.to_string()))
+}
+
+fn locate_id<L, R, F>(
+    list_repo: &L,
+    open_list: &F,
+    todo_list: &TodoList,
+    current: &str,
+    id: &str,
+) -> Result<String>
+where
+    L: TodoListRead,
+    R: TodoItemResolve,
+    F: Fn(&str) -> R,
+{
+    let found = |list: &str| match open_list(list).resolve_id(id) {
+        Ok(_) => true,
+        Err(err) => err.is::<AmbiguousIdError>(),
+    };
+    // numbers only count within a list
+    let is_number = !id.starts_with('0') && id.chars().all(|c| c.is_ascii_digit());
+    if is_number || found(current) {
+        return Ok(current.to_string());
+    }
+    let lists: Vec<String> = todo_list
+        .get_active_lists(list_repo)?
+        .into_iter()
+        .filter(|list| list != current && found(list))
+        .collect();
+    match lists.as_slice() {
+        [] => Ok(current.to_string()),
+        [list] => {
+            log::info!("found '{id}' in list '{list}'");
+            Ok(list.clone())
+        }
+        _ => bail!(
+            "✘ '{id}' matches tasks in the lists {}, choose one with `--list`",
+            lists.join(", ")
+        ),
+    }
+}
+
+fn pick<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    picker: &impl Picker,
+    config: &Config,
+    prompt: &str,
+    entries: &[(TodoItem, Metadata)],
+) -> Result<Option<String>>
+where
+    R: TodoItemQueryColumns,
+{
+    let ctx = FormatContext::from_config(config).with_ids(&todo_list.get_ids(repo)?);
+    let options: Vec<String> = entries
+        .iter()
+        .map(|entry| label(entry, &ctx, config.style.id_mode))
+        .collect();
+    let choice = picker.pick(prompt, &options)?;
+    Ok(choice.map(|idx| entries[idx].0.id.clone()))
+}
+
+// e.g. `01JAB  Fix api  P1  in 2d`, numbers are marked to tell them from the choices
+fn label((item, metadata): &(TodoItem, Metadata), ctx: &FormatContext, id_mode: IdMode) -> String {
+    let id = match id_mode {
+        IdMode::Id => ctx.id(&item.id),
+        IdMode::Number => format!("#{}", metadata.number),
+    };
+    [
+        id,
+        parse_task(&item.task).0,
+        ctx.prio(item.prio),
+        ctx.due(&item.due),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join("  ")
+}
@@ -1,14 +1,24 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use crate::domain::{TodoItem, TodoItemRead, TodoList};
+use crate::application::config::Config;
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, Prio, TodoItem, TodoItemRead, TodoList};
 
-pub fn rnd<R>(repo: &R, todo_list: &TodoList) -> Result<Option<TodoItem>>
+pub fn rnd<R>(
+    repo: &R,
+    todo_list: &TodoList,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<Option<TodoItem>>
 where
     R: TodoItemRead,
 {
-    let rnd_todo = todo_list.get_rnd_item(repo)?;
+    let Some(prio) = Prio::random(&config.priorities) else {
+        bail!("✘ No priority level is named 'RND', see [[priorities]] in the config");
+    };
+    let rnd_todo = todo_list.get_rnd_item(repo, prio)?;
     if rnd_todo.is_none() {
-        println!("✘ Didn't find any items with priority 'RND'.\n▶ Please update the priority of some of your todos to 'RND' so that I can suggest you a random todo among them.");
+        output.notify("✘ Didn't find any items with priority 'RND'.\n▶ Please update the priority of some of your todos to 'RND' so that I can suggest you a random todo among them.");
         Ok(None)
     } else {
         Ok(rnd_todo)
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use ulid::Ulid;
+
+use crate::application::handlers::check_list;
+use crate::application::output::Print;
+use crate::domain::{OutputFormat, TodoItemTransfer, TodoList, TodoListRead};
+
+pub fn move_items<L, R>(
+    list_repo: &L,
+    repo: &R,
+    todo_list: &TodoList,
+    source: &str,
+    target: &str,
+    ids: Vec<String>,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead,
+    R: TodoItemTransfer,
+{
+    check_list(list_repo, todo_list, target)?;
+    let count = ids.len();
+    todo_list.move_items(repo, source, target, ids)?;
+    output.notify(format!("✔ Moved {} to '{target}'", tasks(count)));
+    Ok(())
+}
+
+pub fn copy_items<L, R>(
+    list_repo: &L,
+    repo: &R,
+    todo_list: &TodoList,
+    target: &str,
+    ids: Vec<String>,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead,
+    R: TodoItemTransfer,
+{
+    check_list(list_repo, todo_list, target)?;
+    let copies = todo_list.copy_items(repo, target, ids, || Ulid::new().to_string())?;
+    output.notify(format!("✔ Copied {} to '{target}'", tasks(copies.len())));
+    Ok(())
+}
+
+fn tasks(count: usize) -> String {
+    match count {
+        1 => "1 task".to_string(),
+        count => format!("{count} tasks"),
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::{Context, Result};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::DefaultTerminal;
+
+use crate::application::config::Config;
+use crate::application::handlers::sort_tasks;
+use crate::domain::{
+    FormatContext, ListFilters, TodoItemMetadata, TodoItemQueryColumns, TodoItemUpdate, TodoList,
+    TodoListRead,
+};
+use crate::tui::{self, Action, App};
+
+// `open_list` provides the item repository of a list, so that lists can be switched without
+// leaving the TUI.
+pub fn tui<L, R, F>(
+    list_repo: &L,
+    open_list: F,
+    todo_list: &TodoList,
+    config: &Config,
+    list: &str,
+) -> Result<()>
+where
+    L: TodoListRead,
+    R: TodoItemMetadata + TodoItemQueryColumns + TodoItemUpdate,
+    F: Fn(&str) -> R,
+{
+    // archived lists stay hidden, unless opened explicitly
+    let lists = todo_list
+        .get_collection_details(list_repo)?
+        .into_iter()
+        .filter(|details| !details.archived || details.name == list)
+        .map(|details| details.name)
+        .collect();
+    let mut app = App::new(
+        list,
+        lists,
+        config.style.id_mode,
+        &config.style.due_date_input_format,
+        config.priorities.clone(),
+    );
+    let repo = open_list(list);
+    let ctx = reload(&mut app, &repo, todo_list, config)?;
+    // markup is rendered as plain text, escape codes would garble the screen
+    colored::control::set_override(false);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, app, repo, ctx, open_list, todo_list, config);
+    ratatui::restore();
+    colored::control::unset_override();
+    result
+}
+
+fn event_loop<R, F>(
+    terminal: &mut DefaultTerminal,
+    mut app: App,
+    mut repo: R,
+    mut ctx: FormatContext,
+    open_list: F,
+    todo_list: &TodoList,
+    config: &Config,
+) -> Result<()>
+where
+    R: TodoItemMetadata + TodoItemQueryColumns + TodoItemUpdate,
+    F: Fn(&str) -> R,
+{
+    loop {
+        terminal
+            .draw(|frame| tui::draw(frame, &app, &ctx, &config.theme))
+            .context("✘ Couldn't draw to terminal")?;
+        let Event::Key(key) = event::read().context("✘ Couldn't read terminal event")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let outcome = match app.handle_key(key) {
+            None => continue,
+            Some(Action::Quit) => return Ok(()),
+            Some(Action::Update(id, options)) => todo_list.update_item(&repo, vec![id], options),
+            Some(Action::SwitchList(list)) => {
+                app.switch_list(&list);
+                repo = open_list(&list);
+                Ok(())
+            }
+            Some(Action::Reload) => Ok(()),
+        };
+        match outcome.and_then(|_| reload(&mut app, &repo, todo_list, config)) {
+            Ok(new_ctx) => ctx = new_ctx,
+            Err(err) => app.message = Some(err.to_string()),
+        }
+    }
+}
+
+fn reload<R>(
+    app: &mut App,
+    repo: &R,
+    todo_list: &TodoList,
+    config: &Config,
+) -> Result<FormatContext>
+where
+    R: TodoItemMetadata + TodoItemQueryColumns,
+{
+    let filters = ListFilters {
+        status: Some(app.status_filter()),
+        ..ListFilters::default()
+    };
+    let mut entries = todo_list.get_list_with_metadata(repo, filters)?;
+    sort_tasks(&mut entries, None, config)?;
+    app.set_entries(entries);
+    let ids = todo_list.get_ids(repo)?;
+    Ok(FormatContext::from_config(config).with_ids(&ids))
+}
@@ -1,11 +1,15 @@
+pub mod cancel;
 pub mod clear;
 pub mod close;
 pub mod close_all;
 pub mod open;
+pub mod start;
 pub mod update_item;
 
+pub use cancel::*;
 pub use clear::*;
 pub use close::*;
 pub use close_all::*;
 pub use open::*;
+pub use start::*;
 pub use update_item::*;
@@ -0,0 +1,17 @@
+use anyhow::Result;
+
+use crate::domain::{update::UpdateOptions, Status, TodoItemUpdate, TodoList};
+
+pub fn start<R>(repo: &R, todo_list: &TodoList, ids: Vec<String>) -> Result<()>
+where
+    R: TodoItemUpdate,
+{
+    let options = UpdateOptions {
+        due: None,
+        prio: None,
+        status: Some(Status::InProgress),
+        tag: None,
+    };
+    todo_list.update_item(repo, ids, options)?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+
+use crate::application::config::validate_color;
+use crate::application::handlers::check_list;
+use crate::application::output::Print;
+use crate::domain::{
+    OutputFormat, SelectionRepository, TodoList, TodoListRead, TodoListUpdate, Transactional,
+};
+
+pub fn rename_list<L, S>(
+    list_repo: &L,
+    state_repo: &S,
+    todo_list: &TodoList,
+    list: &str,
+    new_name: &str,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead + TodoListUpdate + Transactional,
+    S: SelectionRepository,
+{
+    check_list(list_repo, todo_list, list)?;
+    list_repo.transaction(|| {
+        todo_list.rename_list(list_repo, list, new_name)?;
+        todo_list.rename_selection(state_repo, list, new_name)
+    })?;
+    output.notify(format!("✔ Renamed '{list}' to '{new_name}'"));
+    Ok(())
+}
+
+pub fn update_list<L>(
+    list_repo: &L,
+    todo_list: &TodoList,
+    list: &str,
+    description: Option<&str>,
+    color: Option<&str>,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead + TodoListUpdate,
+{
+    check_list(list_repo, todo_list, list)?;
+    if let Some(color) = color {
+        validate_color(color, &format!("list '{list}'"))?;
+    }
+    todo_list.update_list(list_repo, list, description, color)?;
+    output.notify(format!("✔ Updated list '{list}'"));
+    Ok(())
+}
+
+pub fn archive_list<L, S>(
+    list_repo: &L,
+    state_repo: &S,
+    todo_list: &TodoList,
+    list: &str,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead + TodoListUpdate,
+    S: SelectionRepository,
+{
+    check_list(list_repo, todo_list, list)?;
+    let selection = todo_list.get_selection(state_repo)?;
+    if selection.current.as_deref() == Some(list) {
+        return Err(anyhow!(
+            "✘ Can't archive the list '{list}' since currently in use"
+        ));
+    }
+    todo_list.archive_list(list_repo, list)?;
+    output.notify(format!(
+        "✔ Archived '{list}', see `todo list collection --archived`"
+    ));
+    Ok(())
+}
+
+pub fn unarchive_list<L>(
+    list_repo: &L,
+    todo_list: &TodoList,
+    list: &str,
+    output: OutputFormat,
+) -> Result<()>
+where
+    L: TodoListRead + TodoListUpdate,
+{
+    check_list(list_repo, todo_list, list)?;
+    todo_list.unarchive_list(list_repo, list)?;
+    output.notify(format!("✔ Restored '{list}'"));
+    Ok(())
+}
@@ -1,11 +1,47 @@
 use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
 
-pub fn whoami() -> Result<()> {
-    let current = std::env::var("CURRENT")?;
-    if current.is_empty() {
-        eprintln!("✘ Currently, no list is active");
-    } else {
-        println!("This is {current}. Ready for duty!");
+use crate::application::output::Print;
+use crate::domain::OutputFormat;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListSource {
+    Flag,
+    Project(PathBuf),
+    Loaded,
+}
+
+impl fmt::Display for ListSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListSource::Flag => write!(f, "--list"),
+            ListSource::Project(path) => write!(f, "{}", path.display()),
+            ListSource::Loaded => write!(f, "todo load"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WhoamiView {
+    list: Option<String>,
+    selected_by: Option<String>,
+}
+
+pub fn whoami(current: Option<(&str, &ListSource)>, output: OutputFormat) -> Result<()> {
+    if !output.is_table() {
+        return output.print_record(&WhoamiView {
+            list: current.map(|(list, _)| list.to_string()),
+            selected_by: current.map(|(_, source)| source.to_string()),
+        });
+    }
+    match current {
+        Some((list, source)) => {
+            println!("This is {list}. Ready for duty!");
+            println!("(selected by {source})");
+        }
+        None => eprintln!("✘ Currently, no list is active"),
     }
     Ok(())
 }
@@ -1,4 +1,6 @@
 pub mod config;
 pub mod editor;
 pub mod handlers;
+pub mod output;
+pub mod picker;
 pub mod run;
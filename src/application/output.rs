@@ -0,0 +1,83 @@
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Display;
+
+use crate::domain::{AmbiguousIdError, Column, GroupBy, OutputFormat, Template};
+use crate::util::parse_task;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderOptions {
+    pub output: OutputFormat,
+    pub template: Option<Template>,
+    pub columns: Option<Vec<Column>>,
+    pub group_by: Option<GroupBy>,
+    pub show_list: bool,
+}
+
+impl From<OutputFormat> for RenderOptions {
+    fn from(output: OutputFormat) -> Self {
+        Self {
+            output,
+            template: None,
+            columns: None,
+            group_by: None,
+            show_list: false,
+        }
+    }
+}
+
+pub trait Print {
+    fn notify(&self, msg: impl Display);
+    fn print_records<T: Serialize>(&self, records: &[T]) -> Result<()>;
+    fn print_record<T: Serialize>(&self, record: &T) -> Result<()>;
+    fn print_error(&self, err: &anyhow::Error);
+}
+
+impl Print for OutputFormat {
+    // status messages go to stderr in machine-readable modes to keep stdout parseable
+    fn notify(&self, msg: impl Display) {
+        if self.is_table() {
+            println!("{msg}");
+        } else {
+            eprintln!("{msg}");
+        }
+    }
+
+    fn print_records<T: Serialize>(&self, records: &[T]) -> Result<()> {
+        let rendered = self.render_records(records)?;
+        if !rendered.is_empty() {
+            println!("{rendered}");
+        }
+        Ok(())
+    }
+
+    fn print_record<T: Serialize>(&self, record: &T) -> Result<()> {
+        let rendered = match self {
+            OutputFormat::Json => serde_json::to_string_pretty(record)?,
+            _ => self.render_records(std::slice::from_ref(record))?,
+        };
+        println!("{rendered}");
+        Ok(())
+    }
+
+    fn print_error(&self, err: &anyhow::Error) {
+        if self.is_json() {
+            let causes: Vec<String> = err.chain().skip(1).map(|cause| cause.to_string()).collect();
+            let mut value = json!({ "error": err.to_string(), "causes": causes });
+            if let Some(ambiguous) = err
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<AmbiguousIdError>())
+            {
+                value["candidates"] = ambiguous
+                    .candidates
+                    .iter()
+                    .map(|item| json!({ "id": item.id, "title": parse_task(&item.task).0 }))
+                    .collect();
+            }
+            eprintln!("{value}");
+        } else {
+            eprintln!("{:?}", err);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use anyhow::Result;
+
+pub trait Picker {
+    // `None` if there is nobody to ask
+    fn pick(&self, prompt: &str, options: &[String]) -> Result<Option<usize>>;
+}
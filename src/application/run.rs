@@ -1,49 +1,53 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use rusqlite::Connection;
 use std::path::PathBuf;
 
-use crate::application::handlers::VersionStatus;
+use crate::application::handlers::{ListSource, VersionStatus};
+use crate::application::output::{Print, RenderOptions};
 use crate::application::{config::Config, handlers};
 use crate::cli::app::Cli;
-use crate::cli::{Cmd, CompletionsCmd, ListSubCmd, Plumbing};
-use crate::domain::{
-    grep::GrepOptions,
-    update::{ClearOptions, UpdateOptions},
-    ListFilters, TodoList,
+use crate::cli::{
+    apply_view, parse_prio, resolve_template, Cmd, CompletionsCmd, ListScopeArgs, ListSubCmd,
+    Plumbing, ViewSubCmd,
+};
+use crate::domain::{grep::GrepOptions, update::ClearOptions, ListFilters, OutputFormat, TodoList};
+use crate::infrastructure::{self, editor, Project, TerminalPicker, UserPaths};
+use crate::persistence::{
+    connect_to_db, migrate, stale_prios, SqlStateRepository, SqlTodoItemRepository,
+    SqlTodoListRepository,
 };
-use crate::infrastructure::{self, editor, UserPaths};
-use crate::persistence::{connect_to_db, SqlTodoItemRepository, SqlTodoListRepository};
 
 pub fn run(app: Cli, config: &Config) -> Result<()> {
+    let output = app.output;
     if let Some(cmd) = app.command {
         match Plumbing::try_from(&cmd) {
-            Ok(plumbing_cmd) => execute_plumbing_cmd(plumbing_cmd, config)?,
-            Err(_) => execute(cmd, config)?,
+            Ok(plumbing_cmd) => execute_plumbing_cmd(plumbing_cmd, config, output)?,
+            Err(_) => execute(cmd, config, output, app.list)?,
         };
     } else {
         let default = Cmd::default();
-        execute(default, config)?;
+        execute(default, config, output, app.list)?;
     }
     Ok(())
 }
 
 fn set_up_repositories(
     conn: &Connection,
-) -> Result<(SqlTodoListRepository<'_>, SqlTodoItemRepository<'_>)> {
-    let current_list = std::env::var("CURRENT")?;
+    current_list: String,
+) -> (SqlTodoListRepository<'_>, SqlTodoItemRepository<'_>) {
     log::info!("currently on list {current_list}");
-    Ok((
+    (
         SqlTodoListRepository::new(conn),
         SqlTodoItemRepository::new(conn, current_list),
-    ))
+    )
 }
 
-fn execute_plumbing_cmd(cmd: Plumbing, config: &Config) -> Result<()> {
+fn execute_plumbing_cmd(cmd: Plumbing, config: &Config, output: OutputFormat) -> Result<()> {
     let user_paths = UserPaths::new();
     let config_file = infrastructure::config::get_todo_config(&user_paths)?;
     match cmd {
         Plumbing::Init => handlers::init(),
-        Plumbing::ShowPaths => handlers::show_paths(),
+        Plumbing::ShowPaths => handlers::show_paths(output),
         Plumbing::CleanData => {
             let db_file = PathBuf::from(config.database.todo_db.clone());
             handlers::clean_data(config_file, db_file)
@@ -52,157 +56,476 @@ fn execute_plumbing_cmd(cmd: Plumbing, config: &Config) -> Result<()> {
             CompletionsCmd::Generate { shell } => handlers::generate_completions(shell),
             CompletionsCmd::Install { shell } => handlers::install_completions(shell),
         },
+        Plumbing::Views(cmd) => match cmd {
+            ViewSubCmd::List => handlers::list_views(config, output),
+            ViewSubCmd::Save(args) => {
+                let name = args.name.clone();
+                handlers::save_view(&name, args.into(), config, output)
+            }
+        },
     }
 }
 
-fn execute(cmd: Cmd, config: &Config) -> Result<()> {
+fn render_options(
+    format: Option<&str>,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<RenderOptions> {
+    let template = format
+        .map(|format| resolve_template(format, config))
+        .transpose()?;
+    if template.is_some() && !output.is_table() {
+        bail!("✘ --format can't be combined with --output");
+    }
+    Ok(RenderOptions {
+        output,
+        template,
+        ..RenderOptions::default()
+    })
+}
+
+fn execute(cmd: Cmd, config: &Config, output: OutputFormat, list: Option<String>) -> Result<()> {
+    let cmd = match cmd {
+        Cmd::View(args) => Cmd::List(args.into_list_args()),
+        cmd => cmd,
+    };
     let user_paths = UserPaths::new();
-    infrastructure::env::load_env(&user_paths)?;
     let editor = editor::SysEditor;
+    let picker = TerminalPicker;
     let mut todo_list = TodoList::new();
-    let db_path = PathBuf::from(config.database.todo_db.clone());
+    let project = infrastructure::find_project(&std::env::current_dir()?)?;
+    let project_db = project.as_ref().and_then(Project::database);
+    let db_path = project_db
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(config.database.todo_db.clone()));
     let conn = connect_to_db(&db_path)?;
-    let (todo_list_repo, todo_item_repo) = set_up_repositories(&conn)?;
+    migrate(&conn)?;
+    // the list a project names may yet have to be created
+    let creates_list = matches!(cmd, Cmd::NewList { .. });
+    let project_list = match project.as_ref().filter(|_| !creates_list) {
+        Some(project) => handlers::project_list(
+            &SqlTodoListRepository::new(&conn),
+            &SqlTodoItemRepository::new(&conn, String::new()),
+            &todo_list,
+            project,
+            output,
+        )?,
+        None => None,
+    };
+    let state_repo = SqlStateRepository::new(&conn);
+    let mut selection = todo_list.get_selection(&state_repo)?;
+    if selection.current.is_none() && project_db.is_none() {
+        if let Some(legacy) = infrastructure::env::read_legacy_selection(&user_paths)? {
+            todo_list.import_selection(&state_repo, &legacy)?;
+            infrastructure::env::retire_legacy_selection(&user_paths)?;
+            selection = todo_list.get_selection(&state_repo)?;
+        }
+    }
+    let current = match (list, project_list, &project) {
+        (Some(list), _, _) => Some((list, ListSource::Flag)),
+        (None, Some(list), Some(project)) => {
+            Some((list, ListSource::Project(project.path.clone())))
+        }
+        _ => selection
+            .current
+            .clone()
+            .map(|list| (list, ListSource::Loaded)),
+    };
+    if current.is_none() && cmd.needs_list() {
+        bail!("✘ No list is loaded, use `todo load <list>`");
+    }
+    let mut current_list = current
+        .as_ref()
+        .map(|(list, _)| list.clone())
+        .unwrap_or_default();
+    if let Some((list, ListSource::Flag)) = &current {
+        handlers::check_list(&SqlTodoListRepository::new(&conn), &todo_list, list)?;
+    } else if current.is_some() {
+        let list = handlers::locate_list(
+            &SqlTodoListRepository::new(&conn),
+            |list| SqlTodoItemRepository::new(&conn, list.to_string()),
+            &todo_list,
+            &current_list,
+            &cmd.task_refs(),
+        )?;
+        if list != current_list {
+            handlers::check_list(&SqlTodoListRepository::new(&conn), &todo_list, &list)?;
+            output.notify(format!("ℹ Using list '{list}'"));
+            current_list = list;
+        }
+    }
+    if cmd.uses_prios() {
+        let lists = std::slice::from_ref(&current_list);
+        for (list, rank) in stale_prios(&conn, &config.priorities, lists)? {
+            output.notify(format!(
+                "⚠ List '{list}' has tasks with priority rank {rank}, which no level in [[priorities]] declares"
+            ));
+        }
+    }
+    let (todo_list_repo, todo_item_repo) = set_up_repositories(&conn, current_list.clone());
+    let current_repos = std::slice::from_ref(&todo_item_repo);
+    let open_lists = |scope: &ListScopeArgs| -> Result<Vec<SqlTodoItemRepository>> {
+        let lists = handlers::lists_in_scope(
+            &todo_list_repo,
+            &todo_list,
+            scope.all_lists,
+            scope.lists.clone(),
+            &current_list,
+        )?;
+        Ok(lists
+            .into_iter()
+            .map(|list| SqlTodoItemRepository::new(&conn, list))
+            .collect())
+    };
+    let resolve_ids = |ids: Vec<String>| {
+        handlers::resolve_ids(&todo_item_repo, &todo_list, &picker, config, ids, output)
+    };
+    let where_ids = |expr: &str| handlers::matching_ids(&todo_item_repo, &todo_list, config, expr);
+    let pick_id = |id: Option<&str>| {
+        handlers::pick_id(&todo_item_repo, &todo_list, &picker, config, id, output)
+    };
     match cmd {
         Cmd::NewList { name, checkout } => {
-            handlers::new_list(&todo_list_repo, &todo_item_repo, &todo_list, &name)?;
+            handlers::new_list(&todo_list_repo, &todo_item_repo, &todo_list, &name, output)?;
             if checkout {
                 log::info!("checking out list '{}'", &name);
-                handlers::load(&todo_list_repo, &mut todo_list, &name)?;
-                println!("✔ Now using '{}'", &name);
+                handlers::load(&todo_list_repo, &state_repo, &todo_list, &name, output)?;
+                output.notify(format!("✔ Now using '{}'", &name));
             };
         }
-        Cmd::DeleteList { name } => handlers::delete_list(&todo_list_repo, &todo_list, name)?,
+        Cmd::DeleteList { name } => {
+            handlers::delete_list(&todo_list_repo, &state_repo, &todo_list, name, output)?
+        }
+        Cmd::RenameList { name, new_name } => handlers::rename_list(
+            &todo_list_repo,
+            &state_repo,
+            &todo_list,
+            &name,
+            &new_name,
+            output,
+        )?,
+        Cmd::UpdateList {
+            name,
+            description,
+            list_color,
+        } => handlers::update_list(
+            &todo_list_repo,
+            &todo_list,
+            &name,
+            description.as_deref(),
+            list_color.as_deref(),
+            output,
+        )?,
+        Cmd::ArchiveList { name } => {
+            handlers::archive_list(&todo_list_repo, &state_repo, &todo_list, &name, output)?
+        }
+        Cmd::UnarchiveList { name } => {
+            handlers::unarchive_list(&todo_list_repo, &todo_list, &name, output)?
+        }
         Cmd::Load { name } => {
             if name == "-" {
-                let previous = std::env::var("PREVIOUS")?;
-                handlers::load(&todo_list_repo, &mut todo_list, &previous)?
+                handlers::load_previous(&todo_list_repo, &state_repo, &todo_list, output)?
             } else {
-                handlers::load(&todo_list_repo, &mut todo_list, &name)?
+                handlers::load(&todo_list_repo, &state_repo, &todo_list, &name, output)?
+            }
+            if let Some(project) = project.as_ref() {
+                if let Some(list) = project.list() {
+                    output.notify(format!(
+                        "⚠ {} still selects '{list}' in this directory",
+                        project.path.display()
+                    ));
+                }
             }
         }
-        Cmd::Whoami => handlers::whoami()?,
+        Cmd::Whoami => handlers::whoami(
+            current
+                .as_ref()
+                .map(|(list, source)| (list.as_str(), source)),
+            output,
+        )?,
         Cmd::Add(args) => {
             let options = args.into_options(config)?;
             handlers::add(&todo_item_repo, &todo_list, &editor, options)?;
             handlers::list(
-                &todo_item_repo,
+                current_repos,
                 &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
         Cmd::List(args) => match args.cmd {
-            Some(ListSubCmd::Collection) => handlers::list_collection(&todo_list_repo, &todo_list)?,
-            Some(ListSubCmd::Tags) => handlers::list_tags(&todo_item_repo, &todo_list)?,
-            None => match args.arg.as_deref() {
-                Some(arg) if arg.starts_with('@') => handlers::list_due_date(
-                    &todo_item_repo,
-                    &todo_list,
-                    config,
-                    arg.to_string(),
-                    args.sort,
-                    ListFilters {
-                        status: args.status,
-                        prio: args.prio,
-                        due: args.due,
-                        tag: args.tag,
-                    },
-                )?,
-                Some(arg) if arg.starts_with('#') => handlers::list_tag(
-                    &todo_item_repo,
-                    &todo_list,
-                    config,
-                    arg.to_string(),
-                    args.sort,
-                    ListFilters {
-                        status: args.status,
-                        prio: args.prio,
-                        due: args.due,
-                        tag: args.tag,
-                    },
-                )?,
-                _ => handlers::list(
-                    &todo_item_repo,
-                    &todo_list,
-                    config,
-                    args.sort,
-                    ListFilters {
-                        status: args.status,
-                        prio: args.prio,
-                        due: args.due,
-                        tag: args.tag,
-                    },
-                )?,
-            },
+            Some(ListSubCmd::Collection { archived, sort }) => handlers::list_collection(
+                &todo_list_repo,
+                |list| SqlTodoItemRepository::new(&conn, list.to_string()),
+                &todo_list,
+                config,
+                handlers::CollectionOptions { archived, sort },
+                &current_list,
+                output,
+            )?,
+            Some(ListSubCmd::Tags) => handlers::list_tags(&todo_item_repo, &todo_list, output)?,
+            None => {
+                let args = apply_view(args, config)?;
+                let prio = args
+                    .prio
+                    .as_deref()
+                    .map(|prio| parse_prio(prio, config))
+                    .transpose()?;
+                let expr = args
+                    .filter
+                    .as_deref()
+                    .map(|expr| handlers::parse_where(expr, config))
+                    .transpose()?;
+                let render = RenderOptions {
+                    columns: args.columns,
+                    group_by: args.group_by,
+                    show_list: args.scope.is_cross_list(),
+                    ..render_options(args.format.as_deref(), config, output)?
+                };
+                let repos = open_lists(&args.scope)?;
+                match args.arg.as_deref() {
+                    Some(arg) if arg.starts_with('@') => handlers::list_due_date(
+                        &repos,
+                        &todo_list,
+                        config,
+                        arg.to_string(),
+                        args.sort,
+                        ListFilters {
+                            status: args.status,
+                            prio,
+                            due: args.due,
+                            tag: args.tag,
+                            expr,
+                        },
+                        &render,
+                    )?,
+                    Some(arg) if arg.starts_with('#') => handlers::list_tag(
+                        &repos,
+                        &todo_list,
+                        config,
+                        arg.to_string(),
+                        args.sort,
+                        ListFilters {
+                            status: args.status,
+                            prio,
+                            due: args.due,
+                            tag: args.tag,
+                            expr,
+                        },
+                        &render,
+                    )?,
+                    _ => handlers::list(
+                        &repos,
+                        &todo_list,
+                        config,
+                        args.sort,
+                        ListFilters {
+                            status: args.status,
+                            prio,
+                            due: args.due,
+                            tag: args.tag,
+                            expr,
+                        },
+                        &render,
+                    )?,
+                }
+            }
         },
-        Cmd::Close { ids } => {
+        Cmd::Board(args) => handlers::board(
+            &todo_item_repo,
+            &todo_list,
+            config,
+            handlers::BoardOptions {
+                group_by: args.group_by,
+                limit: args.limit,
+                status: args.status,
+            },
+            output,
+        )?,
+        Cmd::Tui => handlers::tui(
+            &todo_list_repo,
+            |list| SqlTodoItemRepository::new(&conn, list.to_string()),
+            &todo_list,
+            config,
+            &current_list,
+        )?,
+        Cmd::Close { ids, filter } => {
+            let ids = match filter {
+                Some(expr) => where_ids(&expr)?,
+                None => resolve_ids(ids)?,
+            };
             handlers::close(&todo_item_repo, &todo_list, ids)?;
             handlers::list(
-                &todo_item_repo,
+                current_repos,
                 &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
         Cmd::CloseAll { prio } => {
+            let prio = prio
+                .as_deref()
+                .map(|prio| parse_prio(prio, config))
+                .transpose()?;
             todo_list.close_all(&todo_item_repo, prio)?;
             handlers::list(
-                &todo_item_repo,
+                current_repos,
                 &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
         Cmd::Open { ids } => {
+            let ids = resolve_ids(ids)?;
             handlers::open(&todo_item_repo, &todo_list, ids)?;
             handlers::list(
+                current_repos,
+                &todo_list,
+                config,
+                None,
+                ListFilters::default(),
+                &output.into(),
+            )?
+        }
+        Cmd::Start { ids } => {
+            let ids = resolve_ids(ids)?;
+            handlers::start(&todo_item_repo, &todo_list, ids)?;
+            handlers::list(
+                current_repos,
+                &todo_list,
+                config,
+                None,
+                ListFilters::default(),
+                &output.into(),
+            )?
+        }
+        Cmd::Cancel { ids } => {
+            let ids = resolve_ids(ids)?;
+            handlers::cancel(&todo_item_repo, &todo_list, ids)?;
+            handlers::list(
+                current_repos,
+                &todo_list,
+                config,
+                None,
+                ListFilters::default(),
+                &output.into(),
+            )?
+        }
+        Cmd::Move { ids, to } => {
+            let ids = resolve_ids(ids)?;
+            handlers::move_items(
+                &todo_list_repo,
                 &todo_item_repo,
                 &todo_list,
+                &current_list,
+                &to,
+                ids,
+                output,
+            )?;
+            handlers::list(
+                current_repos,
+                &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
-        Cmd::Delete { id } => handlers::delete(&todo_item_repo, &mut todo_list, &id)?,
-        Cmd::DeleteAll => handlers::delete_all(&todo_item_repo, &mut todo_list)?,
+        Cmd::Copy { ids, to } => {
+            let ids = resolve_ids(ids)?;
+            handlers::copy_items(
+                &todo_list_repo,
+                &todo_item_repo,
+                &todo_list,
+                &to,
+                ids,
+                output,
+            )?;
+            handlers::list(
+                current_repos,
+                &todo_list,
+                config,
+                None,
+                ListFilters::default(),
+                &output.into(),
+            )?
+        }
+        Cmd::Delete { id, filter } => match filter {
+            Some(expr) => {
+                handlers::delete_matching(&todo_item_repo, &mut todo_list, config, &expr, output)?
+            }
+            None => {
+                let id = pick_id(id.as_deref())?;
+                handlers::delete(&todo_item_repo, &mut todo_list, &id)?
+            }
+        },
+        Cmd::DeleteAll => handlers::delete_all(&todo_item_repo, &mut todo_list, output)?,
         Cmd::Grep(args) => {
-            let options = GrepOptions::from(&args);
-            handlers::grep(&todo_item_repo, &todo_list, config, &args.pattern, options)?
+            let options = GrepOptions {
+                filter: args
+                    .filter
+                    .as_deref()
+                    .map(|expr| handlers::parse_where(expr, config))
+                    .transpose()?,
+                ..GrepOptions::from(&args)
+            };
+            handlers::grep(
+                &open_lists(&args.scope)?,
+                &todo_list,
+                config,
+                &args.pattern,
+                options,
+                &RenderOptions {
+                    columns: args.columns.clone(),
+                    show_list: args.scope.is_cross_list(),
+                    ..render_options(args.format.as_deref(), config, output)?
+                },
+            )?
         }
         Cmd::Reword { id, task } => {
+            let id = pick_id(id.as_deref())?;
             handlers::reword(&todo_item_repo, &mut todo_list, &editor, &id, task)?;
-            handlers::show(&todo_item_repo, &todo_list, &id)?
+            handlers::show(&todo_item_repo, &todo_list, config, &id, output)?
         }
         Cmd::RND => {
-            let rnd_item = handlers::rnd(&todo_item_repo, &todo_list)?;
+            let rnd_item = handlers::rnd(&todo_item_repo, &todo_list, config, output)?;
             if let Some(item) = rnd_item {
-                handlers::show(&todo_item_repo, &todo_list, &item.id)?
+                handlers::show(&todo_item_repo, &todo_list, config, &item.id, output)?
             }
         }
+        Cmd::Import { file } => handlers::import(&todo_item_repo, &todo_list, &file, output)?,
         Cmd::Update(args) => {
-            let options = UpdateOptions::from(&args);
-            handlers::update_item(&todo_item_repo, &todo_list, args.ids, options)?;
+            let options = args.to_options(config)?;
+            let ids = match args.filter.as_deref() {
+                Some(expr) => where_ids(expr)?,
+                None => resolve_ids(args.ids)?,
+            };
+            handlers::update_item(&todo_item_repo, &todo_list, ids, options)?;
             handlers::list(
-                &todo_item_repo,
+                current_repos,
                 &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
         Cmd::Clear(args) => {
             let options = ClearOptions::from(&args);
-            handlers::clear(&todo_item_repo, &todo_list, args.ids, options)?;
+            handlers::clear(&todo_item_repo, &todo_list, resolve_ids(args.ids)?, options)?;
             handlers::list(
-                &todo_item_repo,
+                current_repos,
                 &todo_list,
                 config,
                 None,
                 ListFilters::default(),
+                &output.into(),
             )?
         }
         Cmd::Upgrade { version, check } => {
@@ -216,7 +539,10 @@ fn execute(cmd: Cmd, config: &Config) -> Result<()> {
             }
         }
         Cmd::Config => infrastructure::config::edit_config(&editor)?,
-        Cmd::Show { id } => handlers::show(&todo_item_repo, &todo_list, &id)?,
+        Cmd::Show { id } => {
+            let id = pick_id(id.as_deref())?;
+            handlers::show(&todo_item_repo, &todo_list, config, &id, output)?
+        }
         _ => eprintln!("✘ Invalid command"),
     }
     Ok(())
@@ -1,5 +1,6 @@
 use crate::application::config::Config;
-use crate::domain::{add_item::AddOptions, Datetime, Prio, Tag};
+use crate::cli::parse_prio;
+use crate::domain::{add_item::AddOptions, Datetime, Tag};
 use anyhow::Result;
 
 #[derive(clap::Args, Clone, Debug)]
@@ -7,7 +8,7 @@ pub struct AddArgs {
     #[arg(long, short = 'm', help = "Task description")]
     pub task: Option<String>,
     #[arg(long, short = 'p', help = "Priority")]
-    pub prio: Option<Prio>,
+    pub prio: Option<String>,
     #[arg(long, short = 'd', help = "Due date")]
     pub due: Option<String>, // Use String instead of Datetime for config-dependent parsing
     #[arg(long, short = 't', help = "Tag")]
@@ -25,7 +26,10 @@ impl AddArgs {
         };
         Ok(AddOptions {
             task: self.task,
-            prio: self.prio,
+            prio: self
+                .prio
+                .map(|prio| parse_prio(&prio, config))
+                .transpose()?,
             due,
             tag: self.tag,
         })
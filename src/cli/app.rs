@@ -1,5 +1,7 @@
 use crate::cli::Cmd;
-use clap::Parser;
+use crate::domain::OutputFormat;
+use clap::builder::PossibleValue;
+use clap::{Parser, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -8,6 +10,128 @@ use clap::Parser;
     about = "A simple todo cli to help you get things done from the comfort of your terminal"
 )]
 pub struct Cli {
+    #[arg(
+        long,
+        short = 'o',
+        global = true,
+        value_enum,
+        default_value_t,
+        help = "Output format"
+    )]
+    pub output: OutputFormat,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        help = "When to use colors"
+    )]
+    pub color: ColorChoice,
+    #[arg(
+        long,
+        short = 'L',
+        global = true,
+        value_name = "LIST",
+        help = "Use this list instead of the loaded one"
+    )]
+    pub list: Option<String>,
     #[command(subcommand)]
     pub command: Option<Cmd>,
 }
+
+impl Cli {
+    // Read before the config is loaded so that its errors honor `--output` as well. Unlike clap,
+    // this gets past the subcommands and flags of aliases it doesn't know yet, so only the exact
+    // forms of the flag count.
+    pub fn output_format(args: &[String]) -> OutputFormat {
+        let mut args = args.iter().skip(1).map(String::as_str);
+        let mut format = None;
+        while let Some(arg) = args.next() {
+            let value = match arg {
+                "--" => break,
+                "-o" | "--output" => args.next(),
+                _ => arg.strip_prefix("--output=").or(arg.strip_prefix("-o=")),
+            };
+            if let Some(value) = value {
+                format = OutputFormat::from_str(value, true).ok().or(format);
+            }
+        }
+        format.unwrap_or_default()
+    }
+}
+
+impl ValueEnum for OutputFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            OutputFormat::Table,
+            OutputFormat::Json,
+            OutputFormat::Jsonl,
+            OutputFormat::Tsv,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Tsv => "tsv",
+        }))
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color if stdout is a terminal, honoring `NO_COLOR` and `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn apply(&self) {
+        match self {
+            ColorChoice::Auto => colored::control::unset_override(),
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn should_read_output_format_before_expanding_aliases() {
+        let format = Cli::output_format(&args(&["todo", "my-alias", "-o", "json"]));
+        assert_eq!(format, OutputFormat::Json);
+        let format = Cli::output_format(&args(&["todo", "list", "--output=tsv"]));
+        assert_eq!(format, OutputFormat::Tsv);
+        let format = Cli::output_format(&args(&[
+            "todo",
+            "add",
+            "-m",
+            "x",
+            "--due=today",
+            "-o=jsonl",
+        ]));
+        assert_eq!(format, OutputFormat::Jsonl);
+        assert_eq!(Cli::output_format(&args(&["todo"])), OutputFormat::Table);
+    }
+
+    #[test]
+    fn should_only_read_the_exact_output_flag() {
+        let format = Cli::output_format(&args(&["todo", "add", "-m", "x", "-ojson"]));
+        assert_eq!(format, OutputFormat::Table);
+        let format = Cli::output_format(&args(&["todo", "my-alias", "-open", "--outputs=json"]));
+        assert_eq!(format, OutputFormat::Table);
+        let format = Cli::output_format(&args(&["todo", "grep", "--", "-o", "json"]));
+        assert_eq!(format, OutputFormat::Table);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::domain::{GroupBy, StatusFilter};
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct BoardArgs {
+    #[arg(long, value_enum, help = "Group tasks into columns (default: status)")]
+    pub group_by: Option<GroupBy>,
+    #[arg(long, help = "Maximum number of cards per column")]
+    pub limit: Option<usize>,
+    #[arg(long, value_enum, help = "Filter tasks (status)")]
+    pub status: Option<StatusFilter>,
+}
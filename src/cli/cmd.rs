@@ -1,11 +1,15 @@
 use clap::Subcommand;
+use std::path::PathBuf;
 
-use crate::cli::{AddArgs, ClearArgs, CompletionsCmd, GrepArgs, ListArgs, UpdateArgs};
-use crate::domain::{Prio, StatusFilter};
+use crate::cli::{
+    AddArgs, BoardArgs, ClearArgs, CompletionsCmd, GrepArgs, ListArgs, ListScopeArgs, ListSubCmd,
+    UpdateArgs, ViewArgs,
+};
+use crate::domain::StatusFilter;
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum Cmd {
-    /// Initialize the cli in CWD  
+    /// Set up the config, the database and a first list
     Init,
     /// Open config
     Config,
@@ -17,6 +21,24 @@ pub enum Cmd {
     },
     /// Delete a todo list
     DeleteList { name: String },
+    /// Rename a todo list
+    RenameList { name: String, new_name: String },
+    /// Set the description or color of a todo list
+    UpdateList {
+        name: String,
+        #[arg(long, short = 'd', help = "Describe the list, \"\" clears it")]
+        description: Option<String>,
+        #[arg(
+            long,
+            value_name = "COLOR",
+            help = "Color of the list name, \"\" clears it"
+        )]
+        list_color: Option<String>,
+    },
+    /// Hide a todo list from the collection, keeping its tasks
+    ArchiveList { name: String },
+    /// Bring an archived todo list back into the collection
+    UnarchiveList { name: String },
     /// Load a todo list
     Load { name: String },
     /// Print the name of the todo list in use to stdout
@@ -25,31 +47,78 @@ pub enum Cmd {
     Add(AddArgs),
     /// Print the current todo list
     List(ListArgs),
-    /// Show metadata of a task
-    Show { id: String },
+    /// Print the current todo list through a saved view, or manage the views
+    View(ViewArgs),
+    /// Show tasks as a kanban board
+    Board(BoardArgs),
+    /// Browse and edit tasks in a full-screen terminal interface
+    Tui,
+    /// Show metadata of a task (pick one if no id is given)
+    Show { id: Option<String> },
     /// Mark a task as completed
-    Close { ids: Vec<String> },
-    /// Mark all tasks as completed
+    Close {
+        #[arg(conflicts_with = "filter")]
+        ids: Vec<String>,
+        #[arg(
+            long = "where",
+            value_name = "EXPR",
+            help = "Close the tasks matching an expression, e.g. \"tag:backend and due<today\""
+        )]
+        filter: Option<String>,
+    },
+    /// Mark all tasks as completed, except cancelled ones
     CloseAll {
         #[arg(long, short = 'p', help = "Close all PX tasks")]
-        prio: Option<Prio>,
+        prio: Option<String>,
     },
     /// Open a task
     Open { ids: Vec<String> },
-    /// Delete a task
-    Delete { id: String },
+    /// Mark a task as in progress
+    Start { ids: Vec<String> },
+    /// Mark a task as cancelled
+    Cancel { ids: Vec<String> },
+    /// Move tasks to another list
+    Move {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        #[arg(long, value_name = "LIST", help = "Target list")]
+        to: String,
+    },
+    /// Copy tasks to another list
+    Copy {
+        #[arg(required = true)]
+        ids: Vec<String>,
+        #[arg(long, value_name = "LIST", help = "Target list")]
+        to: String,
+    },
+    /// Delete a task (pick one if no id is given)
+    Delete {
+        #[arg(conflicts_with = "filter")]
+        id: Option<String>,
+        #[arg(
+            long = "where",
+            value_name = "EXPR",
+            help = "Delete the tasks matching an expression, e.g. \"status:cancelled\""
+        )]
+        filter: Option<String>,
+    },
     /// Delete all tasks in the current todo list
     DeleteAll,
     /// Search a pattern inside todos
     Grep(GrepArgs),
-    /// Reword a task
+    /// Reword a task (pick one if no id is given)
     Reword {
-        id: String,
+        id: Option<String>,
         #[arg(long, short = 'm', help = "Task description")]
         task: Option<String>,
     },
     /// Get a random todo among those with prio = RNG
     RND,
+    /// Import issues from a GitHub/GitLab JSON dump
+    Import {
+        #[arg(help = "Path to the JSON file ('-' for stdin)")]
+        file: PathBuf,
+    },
     /// Update the fields of an item
     Update(UpdateArgs),
     /// Clear due, prio or the tag column
@@ -80,8 +149,76 @@ impl Default for Cmd {
             prio: None,
             due: None,
             tag: None,
+            filter: None,
             sort: None,
+            format: None,
+            columns: None,
+            group_by: None,
+            view: None,
+            scope: ListScopeArgs::default(),
             arg: None,
         })
     }
 }
+
+impl Cmd {
+    pub fn needs_list(&self) -> bool {
+        match self {
+            Cmd::List(args) => {
+                !matches!(args.cmd, Some(ListSubCmd::Collection { .. }))
+                    && !args.scope.is_cross_list()
+            }
+            Cmd::Grep(args) => !args.scope.is_cross_list(),
+            Cmd::View(args) => !args.scope.is_cross_list(),
+            Cmd::Init
+            | Cmd::Config
+            | Cmd::NewList { .. }
+            | Cmd::DeleteList { .. }
+            | Cmd::RenameList { .. }
+            | Cmd::UpdateList { .. }
+            | Cmd::ArchiveList { .. }
+            | Cmd::UnarchiveList { .. }
+            | Cmd::Load { .. }
+            | Cmd::Whoami
+            | Cmd::Upgrade { .. }
+            | Cmd::ShowPaths
+            | Cmd::CleanData
+            | Cmd::Completions { .. } => false,
+            _ => true,
+        }
+    }
+
+    // the commands which point out stale priority ranks
+    pub fn uses_prios(&self) -> bool {
+        match self {
+            Cmd::List(args) => !matches!(args.cmd, Some(ListSubCmd::Collection { .. })),
+            Cmd::View(_)
+            | Cmd::Add(_)
+            | Cmd::Update(_)
+            | Cmd::Board(_)
+            | Cmd::Tui
+            | Cmd::Grep(_)
+            | Cmd::Show { .. }
+            | Cmd::CloseAll { .. }
+            | Cmd::RND => true,
+            _ => false,
+        }
+    }
+
+    pub fn task_refs(&self) -> Vec<String> {
+        match self {
+            Cmd::Close { ids, .. }
+            | Cmd::Open { ids }
+            | Cmd::Start { ids }
+            | Cmd::Cancel { ids }
+            | Cmd::Move { ids, .. }
+            | Cmd::Copy { ids, .. } => ids.clone(),
+            Cmd::Update(args) => args.ids.clone(),
+            Cmd::Clear(args) => args.ids.clone(),
+            Cmd::Show { id } | Cmd::Delete { id, .. } | Cmd::Reword { id, .. } => {
+                id.iter().cloned().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
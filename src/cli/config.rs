@@ -1,4 +1,13 @@
-use crate::application::config::Config;
+use anyhow::{anyhow, bail, Context, Result};
+use std::str::FromStr;
+
+use crate::application::config::{Config, ViewConfig};
+use crate::application::handlers::parse_where;
+use crate::cli::ListArgs;
+use crate::domain::{Prio, SortSpec, Template};
+
+// the subcommands of `todo view`, which would shadow a view of the same name
+const RESERVED_VIEW_NAMES: [&str; 2] = ["list", "save"];
 
 pub fn expand_alias(args: Vec<String>, config: &Config) -> Vec<String> {
     match args.as_slice() {
@@ -19,10 +28,72 @@ pub fn expand_alias(args: Vec<String>, config: &Config) -> Vec<String> {
     }
 }
 
+// named templates take precedence over literal ones
+pub fn resolve_template(format: &str, config: &Config) -> Result<Template> {
+    let template = config
+        .templates
+        .as_ref()
+        .and_then(|templates| templates.get(format))
+        .map(String::as_str)
+        .unwrap_or(format);
+    Template::from_str(template).context(format!("✘ Invalid template '{template}'"))
+}
+
+pub fn parse_prio(prio: &str, config: &Config) -> Result<Prio> {
+    Prio::parse_with(&config.priorities, prio).context("✘ Invalid priority")
+}
+
+// A `--where` given along with `--view` narrows down the view's expression instead of
+// replacing it.
+pub fn apply_view(args: ListArgs, config: &Config) -> Result<ListArgs> {
+    let Some(name) = args.view.as_deref() else {
+        return Ok(args);
+    };
+    let view = config
+        .views
+        .get(name)
+        .ok_or(anyhow!(
+            "✘ Unknown view '{name}', see [views] in the config"
+        ))?
+        .clone();
+    let filter = match (view.filter, args.filter) {
+        (Some(view), Some(filter)) => Some(format!("({view}) and ({filter})")),
+        (view, filter) => filter.or(view),
+    };
+    Ok(ListArgs {
+        filter,
+        status: args.status.or(view.status),
+        sort: args.sort.or(view.sort),
+        format: args.format.or(view.format),
+        columns: args.columns.or(view.columns),
+        group_by: args.group_by.or(view.group_by),
+        ..args
+    })
+}
+
+pub fn check_view(name: &str, view: &ViewConfig, config: &Config) -> Result<()> {
+    if RESERVED_VIEW_NAMES.contains(&name) {
+        bail!("✘ '{name}' is a subcommand of `todo view`, pick another name for the view");
+    }
+    if let Some(expr) = &view.filter {
+        parse_where(expr, config)?;
+    }
+    if let Some(sort) = &view.sort {
+        SortSpec::from_str(sort).context(format!("✘ Invalid sort '{sort}'"))?;
+    }
+    if let Some(format) = &view.format {
+        resolve_template(format, config)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
-    use crate::application::config::{Database, Style};
+    use crate::application::config::{BoardConfig, Database, IdMode, Style, Theme};
+    use crate::cli::{app::Cli, Cmd, ViewArgs, ViewSubCmd};
+    use crate::domain::{GroupBy, PrioLevel, StatusFilter};
+    use clap::Parser;
     use std::collections::HashMap;
 
     #[test]
@@ -33,14 +104,23 @@ pub mod test {
             },
             style: Style {
                 id_length: 1,
+                id_mode: IdMode::Id,
                 due_date_display_format: String::new(),
                 due_date_input_format: String::new(),
                 show_due: true,
                 show_tag: true,
                 sort_by: String::new(),
                 table: String::new(),
+                columns: None,
+                group_by: None,
+                due_horizon: 7,
             },
             aliases: None,
+            templates: None,
+            theme: Theme::default(),
+            priorities: PrioLevel::defaults(),
+            board: BoardConfig::default(),
+            views: HashMap::new(),
         };
         let args: Vec<String> = vec!["program".into(), "add --due=today".into()];
         let expanded = expand_alias(args, &config);
@@ -58,18 +138,205 @@ pub mod test {
             },
             style: Style {
                 id_length: 1,
+                id_mode: IdMode::Id,
                 due_date_display_format: String::new(),
                 due_date_input_format: String::new(),
                 show_due: true,
                 show_tag: true,
                 sort_by: String::new(),
                 table: String::new(),
+                columns: None,
+                group_by: None,
+                due_horizon: 7,
             },
             aliases: Some(aliases),
+            templates: None,
+            theme: Theme::default(),
+            priorities: PrioLevel::defaults(),
+            board: BoardConfig::default(),
+            views: HashMap::new(),
         };
         let args: Vec<String> = vec!["program".into(), "p1".into(), "--due=today".into()];
         let expanded = expand_alias(args, &config);
         let expected = vec!["program", "add", "--prio=p1", "--due=today"];
         assert_eq!(expanded, expected);
     }
+
+    #[test]
+    fn should_resolve_named_template_before_literal() -> Result<()> {
+        let mut templates = HashMap::new();
+        templates.insert("short".to_string(), "{id:4} {title}".to_string());
+        let config = Config {
+            database: Database {
+                todo_db: String::new(),
+            },
+            style: Style {
+                id_length: 1,
+                id_mode: IdMode::Id,
+                due_date_display_format: String::new(),
+                due_date_input_format: String::new(),
+                show_due: true,
+                show_tag: true,
+                sort_by: String::new(),
+                table: String::new(),
+                columns: None,
+                group_by: None,
+                due_horizon: 7,
+            },
+            aliases: None,
+            templates: Some(templates),
+            theme: Theme::default(),
+            priorities: PrioLevel::defaults(),
+            board: BoardConfig::default(),
+            views: HashMap::new(),
+        };
+        assert_eq!(
+            resolve_template("short", &config)?,
+            Template::from_str("{id:4} {title}")?
+        );
+        assert_eq!(
+            resolve_template("{prio}", &config)?,
+            Template::from_str("{prio}")?
+        );
+        assert!(resolve_template("{nope}", &config).is_err());
+        Ok(())
+    }
+
+    fn config_with_views(views: HashMap<String, ViewConfig>) -> Config {
+        Config {
+            database: Database {
+                todo_db: String::new(),
+            },
+            style: Style {
+                id_length: 1,
+                id_mode: IdMode::Id,
+                due_date_display_format: String::new(),
+                due_date_input_format: String::new(),
+                show_due: true,
+                show_tag: true,
+                sort_by: String::new(),
+                table: String::new(),
+                columns: None,
+                group_by: None,
+                due_horizon: 7,
+            },
+            aliases: None,
+            templates: None,
+            theme: Theme::default(),
+            priorities: PrioLevel::defaults(),
+            board: BoardConfig::default(),
+            views,
+        }
+    }
+
+    fn list_args(args: &[&str]) -> ListArgs {
+        let cli = Cli::parse_from(["todo", "list"].iter().chain(args));
+        match cli.command {
+            Some(Cmd::List(args)) => args,
+            cmd => panic!("expected a list command, got {cmd:?}"),
+        }
+    }
+
+    fn standup() -> Config {
+        let mut views = HashMap::new();
+        views.insert(
+            "standup".to_string(),
+            ViewConfig {
+                filter: Some("prio:p1".to_string()),
+                status: Some(StatusFilter::All),
+                sort: Some("due".to_string()),
+                group_by: Some(GroupBy::Tag),
+                ..ViewConfig::default()
+            },
+        );
+        config_with_views(views)
+    }
+
+    #[test]
+    fn should_apply_view() -> Result<()> {
+        let args = apply_view(list_args(&["--view", "standup"]), &standup())?;
+        assert_eq!(args.filter.as_deref(), Some("prio:p1"));
+        assert_eq!(args.status, Some(StatusFilter::All));
+        assert_eq!(args.sort.as_deref(), Some("due"));
+        assert_eq!(args.group_by, Some(GroupBy::Tag));
+        assert_eq!(args.columns, None);
+        Ok(())
+    }
+
+    #[test]
+    fn should_prefer_flags_over_view() -> Result<()> {
+        let args = list_args(&["--view", "standup", "-s", "-prio", "--where", "tag:x"]);
+        let args = apply_view(args, &standup())?;
+        assert_eq!(args.sort.as_deref(), Some("-prio"));
+        assert_eq!(args.filter.as_deref(), Some("(prio:p1) and (tag:x)"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_view() {
+        let err = apply_view(list_args(&["--view", "nope"]), &standup()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "✘ Unknown view 'nope', see [views] in the config"
+        );
+    }
+
+    #[test]
+    fn should_reject_reserved_view_names() {
+        for name in ["list", "save"] {
+            let view = ViewConfig {
+                sort: Some("due".to_string()),
+                ..ViewConfig::default()
+            };
+            let err = check_view(name, &view, &standup()).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "✘ '{name}' is a subcommand of `todo view`, pick another name for the view"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_view_flags() {
+        let config = standup();
+        let check = |view: ViewConfig| check_view("mine", &view, &config);
+        assert!(check(ViewConfig {
+            filter: Some("prio:p1 and".to_string()),
+            ..ViewConfig::default()
+        })
+        .is_err());
+        assert!(check(ViewConfig {
+            sort: Some("prio,nope".to_string()),
+            ..ViewConfig::default()
+        })
+        .is_err());
+        assert!(check(ViewConfig {
+            format: Some("{nope}".to_string()),
+            ..ViewConfig::default()
+        })
+        .is_err());
+        assert!(check(ViewConfig {
+            filter: Some("prio:p1".to_string()),
+            sort: Some("-due".to_string()),
+            format: Some("{id} {title}".to_string()),
+            ..ViewConfig::default()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn should_parse_descending_sort() {
+        let args = list_args(&["--sort", "-title,prio"]);
+        assert_eq!(args.sort.as_deref(), Some("-title,prio"));
+        let cli = Cli::parse_from(["todo", "view", "save", "mine", "--sort", "-title,prio"]);
+        match cli.command {
+            Some(Cmd::View(ViewArgs {
+                cmd: Some(ViewSubCmd::Save(args)),
+                ..
+            })) => assert_eq!(args.sort.as_deref(), Some("-title,prio")),
+            cmd => panic!("expected a view save command, got {cmd:?}"),
+        }
+    }
 }
@@ -1,16 +1,40 @@
-use crate::domain::grep::GrepOptions;
+use crate::cli::ListScopeArgs;
+use crate::domain::{grep::GrepOptions, Column};
 
 #[derive(clap::Args, Clone, Debug)]
 pub struct GrepArgs {
     pub pattern: String,
     #[arg(long, short = 'i', help = "Search case-insensitively")]
     pub ignore: bool,
+    #[arg(
+        long,
+        short = 'f',
+        help = "Render each task with a template (or a named one)"
+    )]
+    pub format: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Table columns, e.g. id,prio,title"
+    )]
+    pub columns: Option<Vec<Column>>,
+    #[arg(
+        long = "where",
+        value_name = "EXPR",
+        help = "Filter tasks with an expression, e.g. \"prio:p1 and due<eow\""
+    )]
+    pub filter: Option<String>,
+    #[command(flatten)]
+    pub scope: ListScopeArgs,
 }
 
 impl From<&GrepArgs> for GrepOptions {
     fn from(args: &GrepArgs) -> Self {
         Self {
             case_insensitive: args.ignore,
+            // parsed along with the config, see `handlers::parse_where`
+            filter: None,
         }
     }
 }
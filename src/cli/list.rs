@@ -1,28 +1,95 @@
-use crate::domain::{Datetime, Prio, StatusFilter, Tag};
+use crate::domain::{Column, Datetime, GroupBy, StatusFilter, Tag};
 use clap::Subcommand;
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum ListSubCmd {
     /// List collection (of todo lists)
-    Collection,
+    Collection {
+        #[arg(long, help = "Show the archived lists instead")]
+        archived: bool,
+        #[arg(
+            long,
+            short = 's',
+            allow_hyphen_values = true,
+            help = "Sort lists, e.g. -overdue,name (name, open, closed, overdue, today, activity)"
+        )]
+        sort: Option<String>,
+    },
     /// List all tags present in the todo list
     Tags,
 }
 
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct ListScopeArgs {
+    #[arg(
+        long,
+        conflicts_with = "lists",
+        help = "Query every list of the collection"
+    )]
+    pub all_lists: bool,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "LISTS",
+        help = "Query these lists, e.g. work,home"
+    )]
+    pub lists: Option<Vec<String>>,
+}
+
+impl ListScopeArgs {
+    pub fn is_cross_list(&self) -> bool {
+        self.all_lists || self.lists.is_some()
+    }
+}
+
 #[derive(clap::Args, Clone, Debug)]
 pub struct ListArgs {
     #[command(subcommand)]
     pub cmd: Option<ListSubCmd>,
     #[arg(long, value_enum, help = "Filter tasks (status)")]
     pub status: Option<StatusFilter>,
-    #[arg(long, value_enum, help = "Filter tasks (prio)")]
-    pub prio: Option<Prio>,
+    #[arg(long, help = "Filter tasks (prio)")]
+    pub prio: Option<String>,
     #[arg(long, value_enum, help = "Filter tasks (prio)")]
     pub due: Option<Datetime>,
     #[arg(long, value_enum, help = "Filter tasks (prio)")]
     pub tag: Option<Tag>,
-    #[arg(long, short = 's', help = "Sort tasks")]
+    #[arg(
+        long = "where",
+        value_name = "EXPR",
+        help = "Filter tasks with an expression, e.g. \"prio:p1 and due<eow\""
+    )]
+    pub filter: Option<String>,
+    #[arg(
+        long,
+        short = 's',
+        allow_hyphen_values = true,
+        help = "Sort tasks, e.g. prio,due,-created"
+    )]
     pub sort: Option<String>,
+    #[arg(
+        long,
+        short = 'f',
+        help = "Render each task with a template (or a named one)"
+    )]
+    pub format: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Table columns, e.g. id,prio,title"
+    )]
+    pub columns: Option<Vec<Column>>,
+    #[arg(long, value_enum, help = "Render one table per group")]
+    pub group_by: Option<GroupBy>,
+    #[arg(
+        long,
+        value_name = "VIEW",
+        help = "Start from a view saved in the config, flags given as well take precedence"
+    )]
+    pub view: Option<String>,
+    #[command(flatten)]
+    pub scope: ListScopeArgs,
     /// Optional positional argument like @today or #work
     pub arg: Option<String>,
 }
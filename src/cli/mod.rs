@@ -1,5 +1,6 @@
 pub mod add;
 pub mod app;
+pub mod board;
 pub mod clear;
 pub mod cmd;
 pub mod completions;
@@ -8,8 +9,10 @@ pub mod grep;
 pub mod list;
 pub mod plumbing;
 pub mod update;
+pub mod view;
 
 pub use add::*;
+pub use board::*;
 pub use clear::*;
 pub use cmd::*;
 pub use completions::*;
@@ -18,3 +21,4 @@ pub use grep::*;
 pub use list::*;
 pub use plumbing::*;
 pub use update::*;
+pub use view::*;
@@ -1,4 +1,4 @@
-use crate::cli::{Cmd, CompletionsCmd};
+use crate::cli::{Cmd, CompletionsCmd, ViewSubCmd};
 use anyhow::Result;
 
 #[derive(Debug)]
@@ -7,6 +7,7 @@ pub enum Plumbing {
     CleanData,
     Init,
     Completions(CompletionsCmd),
+    Views(ViewSubCmd),
 }
 
 impl TryFrom<&Cmd> for Plumbing {
@@ -18,6 +19,11 @@ impl TryFrom<&Cmd> for Plumbing {
             Cmd::CleanData => Ok(Plumbing::CleanData),
             Cmd::ShowPaths => Ok(Plumbing::ShowPaths),
             Cmd::Completions { cmd } => Ok(Plumbing::Completions(cmd.clone())),
+            Cmd::View(args) => match (&args.cmd, &args.name) {
+                (Some(cmd), _) => Ok(Plumbing::Views(cmd.clone())),
+                (None, None) => Ok(Plumbing::Views(ViewSubCmd::List)),
+                (None, Some(_)) => Err(()),
+            },
             _ => Err(()),
         }
     }
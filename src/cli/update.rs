@@ -1,25 +1,40 @@
-use crate::domain::{update::UpdateOptions, Datetime, Prio, Status, Tag};
+use anyhow::Result;
+
+use crate::application::config::Config;
+use crate::cli::parse_prio;
+use crate::domain::{update::UpdateOptions, Datetime, Status, Tag};
 
 #[derive(clap::Args, Clone, Debug)]
 pub struct UpdateArgs {
+    #[arg(conflicts_with = "filter")]
     pub ids: Vec<String>,
+    #[arg(
+        long = "where",
+        value_name = "EXPR",
+        help = "Update the tasks matching an expression, e.g. \"tag:infra and not prio:p1\""
+    )]
+    pub filter: Option<String>,
     #[arg(long, short = 'd', help = "Update the due date")]
     pub due: Option<Datetime>,
     #[arg(long, short = 'p', help = "Update the priority")]
-    pub prio: Option<Prio>,
+    pub prio: Option<String>,
     #[arg(long, short = 's', help = "Update the status")]
     pub status: Option<Status>,
     #[arg(long, short = 't', help = "Update the tag")]
     pub tag: Option<Tag>,
 }
 
-impl From<&UpdateArgs> for UpdateOptions {
-    fn from(args: &UpdateArgs) -> Self {
-        Self {
-            due: args.due,
-            prio: args.prio,
-            status: args.status,
-            tag: args.tag.clone(),
-        }
+impl UpdateArgs {
+    pub fn to_options(&self, config: &Config) -> Result<UpdateOptions> {
+        Ok(UpdateOptions {
+            due: self.due,
+            prio: self
+                .prio
+                .as_deref()
+                .map(|prio| parse_prio(prio, config))
+                .transpose()?,
+            status: self.status,
+            tag: self.tag.clone(),
+        })
     }
 }
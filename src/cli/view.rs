@@ -0,0 +1,93 @@
+use clap::Subcommand;
+
+use crate::application::config::ViewConfig;
+use crate::cli::{ListArgs, ListScopeArgs};
+use crate::domain::{Column, GroupBy, StatusFilter};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ViewSubCmd {
+    /// Save the given flags as a view, replacing one of the same name
+    Save(SaveViewArgs),
+    /// List the views saved in the config
+    List,
+}
+
+#[derive(clap::Args, Clone, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ViewArgs {
+    #[command(subcommand)]
+    pub cmd: Option<ViewSubCmd>,
+    /// Name of the view to show
+    #[arg(value_name = "VIEW")]
+    pub name: Option<String>,
+    #[command(flatten)]
+    pub scope: ListScopeArgs,
+}
+
+impl ViewArgs {
+    pub fn into_list_args(self) -> ListArgs {
+        ListArgs {
+            cmd: None,
+            status: None,
+            prio: None,
+            due: None,
+            tag: None,
+            filter: None,
+            sort: None,
+            format: None,
+            columns: None,
+            group_by: None,
+            view: self.name,
+            scope: self.scope,
+            arg: None,
+        }
+    }
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct SaveViewArgs {
+    pub name: String,
+    #[arg(long, value_enum, help = "Filter tasks (status)")]
+    pub status: Option<StatusFilter>,
+    #[arg(
+        long = "where",
+        value_name = "EXPR",
+        help = "Filter tasks with an expression, e.g. \"prio:p1 and due<eow\""
+    )]
+    pub filter: Option<String>,
+    #[arg(
+        long,
+        short = 's',
+        allow_hyphen_values = true,
+        help = "Sort tasks, e.g. prio,due,-created"
+    )]
+    pub sort: Option<String>,
+    #[arg(
+        long,
+        short = 'f',
+        help = "Render each task with a template (or a named one)"
+    )]
+    pub format: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Table columns, e.g. id,prio,title"
+    )]
+    pub columns: Option<Vec<Column>>,
+    #[arg(long, value_enum, help = "Render one table per group")]
+    pub group_by: Option<GroupBy>,
+}
+
+impl From<SaveViewArgs> for ViewConfig {
+    fn from(args: SaveViewArgs) -> Self {
+        Self {
+            filter: args.filter,
+            status: args.status,
+            sort: args.sort,
+            group_by: args.group_by,
+            columns: args.columns,
+            format: args.format,
+        }
+    }
+}
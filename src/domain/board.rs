@@ -0,0 +1,225 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Modify, Width},
+    Table,
+};
+
+use crate::application::config::{paint, Config, IdMode};
+use crate::domain::table::{apply_table_style, terminal_width};
+use crate::domain::{FormatContext, Group, Metadata, PrioLevel, TodoEntryView, TodoItem};
+use crate::util::parse_task;
+
+const MIN_LANE_WIDTH: usize = 12;
+
+// a board column, named lane to avoid confusion with table columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lane {
+    pub title: String,
+    pub cards: Vec<(TodoItem, Metadata)>,
+    pub wip_limit: Option<usize>,
+}
+
+impl Lane {
+    pub fn is_over_limit(&self) -> bool {
+        self.wip_limit.is_some_and(|limit| self.cards.len() > limit)
+    }
+
+    fn header(&self) -> String {
+        match self.wip_limit {
+            Some(limit) => format!("{} ({}/{limit})", self.title, self.cards.len()),
+            None => format!("{} ({})", self.title, self.cards.len()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CardView {
+    pub lane: String,
+    #[serde(flatten)]
+    pub entry: TodoEntryView,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    pub lanes: Vec<Lane>,
+}
+
+impl Board {
+    // Lanes named in `order` come first, even when empty. Titles match case-insensitively, as do
+    // the keys of `wip_limits`.
+    pub fn new(groups: Vec<Group>, order: &[String], wip_limits: &HashMap<String, usize>) -> Self {
+        let mut groups = groups;
+        let mut lanes: Vec<Lane> = Vec::new();
+        for title in order {
+            let cards = match groups
+                .iter()
+                .position(|group| group.title.eq_ignore_ascii_case(title))
+            {
+                Some(idx) => groups.remove(idx).entries,
+                None => Vec::new(),
+            };
+            lanes.push(Lane {
+                title: title.clone(),
+                cards,
+                wip_limit: None,
+            });
+        }
+        lanes.extend(groups.into_iter().map(|group| Lane {
+            title: group.title,
+            cards: group.entries,
+            wip_limit: None,
+        }));
+        for lane in lanes.iter_mut() {
+            lane.wip_limit = wip_limits
+                .iter()
+                .find(|(title, _)| title.eq_ignore_ascii_case(&lane.title))
+                .map(|(_, limit)| *limit);
+        }
+        Self { lanes }
+    }
+
+    pub fn cards(&self, levels: &[PrioLevel]) -> Vec<CardView> {
+        self.lanes
+            .iter()
+            .flat_map(|lane| {
+                lane.cards.iter().map(|(item, metadata)| CardView {
+                    lane: lane.title.clone(),
+                    entry: TodoEntryView::new(item, metadata, levels),
+                })
+            })
+            .collect()
+    }
+
+    pub fn table(&self, ctx: &FormatContext, config: &Config, limit: Option<usize>) -> Table {
+        let mut builder = Builder::default();
+        builder.push_record(self.lanes.iter().map(|lane| {
+            let header = lane.header();
+            if lane.is_over_limit() {
+                paint(&header, &config.theme.overdue)
+            } else {
+                header
+            }
+        }));
+        let rows = self
+            .lanes
+            .iter()
+            .map(|lane| match visible_cards(lane.cards.len(), limit) {
+                (shown, 0) => shown,
+                (shown, _) => shown + 1,
+            })
+            .max()
+            .unwrap_or_default();
+        for row in 0..rows {
+            builder.push_record(self.lanes.iter().map(|lane| {
+                let (shown, hidden) = visible_cards(lane.cards.len(), limit);
+                match lane.cards.get(row) {
+                    Some((item, metadata)) if row < shown => card(item, metadata, ctx, config),
+                    _ if row == shown && hidden > 0 => format!("… {hidden} more"),
+                    _ => String::new(),
+                }
+            }));
+        }
+        let mut table = builder.build();
+        apply_table_style(&mut table, config);
+        if let Some(width) = terminal_width() {
+            let lanes = self.lanes.len().max(1);
+            // each lane has a border and one space of padding on either side
+            let lane_width = (width.saturating_sub(1) / lanes)
+                .saturating_sub(3)
+                .max(MIN_LANE_WIDTH);
+            table.with(Modify::new(Columns::new(..)).with(Width::wrap(lane_width).keep_words()));
+        }
+        table
+    }
+}
+
+// the cards shown and the ones cut off, which a "… n more" row stands for
+fn visible_cards(cards: usize, limit: Option<usize>) -> (usize, usize) {
+    match limit {
+        Some(limit) if cards > limit => (limit, cards - limit),
+        _ => (cards, 0),
+    }
+}
+
+fn card(item: &TodoItem, metadata: &Metadata, ctx: &FormatContext, config: &Config) -> String {
+    let (title, _) = parse_task(&item.task);
+    let id = match config.style.id_mode {
+        IdMode::Id => ctx.id(&item.id),
+        IdMode::Number => metadata.number.to_string(),
+    };
+    let prio = match ctx.prio_level(item.prio) {
+        Some(level) => paint(&level.name, &level.color),
+        None => ctx.prio(item.prio),
+    };
+    let due = if item.status.is_active() && ctx.is_overdue(&item.due) {
+        paint(&ctx.due(&item.due), &config.theme.overdue)
+    } else {
+        ctx.due(&item.due)
+    };
+    let details: Vec<String> = [paint(&id, &config.theme.id), prio, due]
+        .into_iter()
+        .filter(|detail| !detail.is_empty())
+        .collect();
+    format!("{title}\n{}", details.join("  "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::todo::test_support::entry;
+
+    fn group(title: &str, ids: &[&str]) -> Group {
+        Group {
+            title: title.to_string(),
+            entries: ids.iter().map(|id| entry(id).build()).collect(),
+        }
+    }
+
+    #[test]
+    fn should_order_lanes_and_keep_configured_empty_ones() {
+        let groups = vec![
+            group("Open", &["a", "b"]),
+            group("Done", &["c"]),
+            group("Blocked", &["d"]),
+        ];
+        let order = vec!["in progress".to_string(), "Done".to_string()];
+        let board = Board::new(groups, &order, &HashMap::new());
+        let titles: Vec<&str> = board.lanes.iter().map(|lane| lane.title.as_str()).collect();
+        assert_eq!(titles, vec!["in progress", "Done", "Open", "Blocked"]);
+        assert!(board.lanes[0].cards.is_empty());
+    }
+
+    #[test]
+    fn should_flag_lanes_over_their_wip_limit() {
+        let groups = vec![group("Open", &["a", "b", "c"]), group("Done", &["d"])];
+        let wip_limits = HashMap::from([("open".to_string(), 2), ("Done".to_string(), 1)]);
+        let board = Board::new(groups, &[], &wip_limits);
+        assert!(board.lanes[0].is_over_limit());
+        assert!(!board.lanes[1].is_over_limit());
+        assert_eq!(board.lanes[0].header(), "Open (3/2)");
+    }
+
+    #[test]
+    fn should_list_cards_with_their_lane() {
+        let groups = vec![group("Open", &["a", "b"]), group("Done", &["c"])];
+        let board = Board::new(groups, &[], &HashMap::new());
+        let cards: Vec<(String, String)> = board
+            .cards(&PrioLevel::defaults())
+            .into_iter()
+            .map(|card| (card.lane, card.entry.item.id))
+            .collect();
+        let expected = [("Open", "a"), ("Open", "b"), ("Done", "c")]
+            .map(|(lane, id)| (lane.to_string(), id.to_string()));
+        assert_eq!(cards, expected);
+    }
+
+    #[test]
+    fn should_cut_off_cards_beyond_limit() {
+        assert_eq!(visible_cards(5, Some(3)), (3, 2));
+        assert_eq!(visible_cards(4, Some(3)), (3, 1));
+        assert_eq!(visible_cards(3, Some(3)), (3, 0));
+        assert_eq!(visible_cards(5, None), (5, 0));
+    }
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::application::config::IdMode;
+use crate::domain::{Datetime, FormatContext, Metadata, Prio, TodoItem};
+use crate::util::parse_task;
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(clap::ValueEnum, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Id,
+    Number,
+    Title,
+    Status,
+    Prio,
+    Due,
+    Tag,
+    Created,
+    Updated,
+    Age,
+    Urgency,
+    List,
+}
+
+impl Column {
+    pub fn defaults(id_mode: IdMode, show_due: bool, show_tag: bool) -> Vec<Column> {
+        let id = match id_mode {
+            IdMode::Id => Column::Id,
+            IdMode::Number => Column::Number,
+        };
+        let mut columns = vec![id, Column::Title, Column::Status, Column::Prio];
+        if show_due {
+            columns.push(Column::Due);
+        }
+        if show_tag {
+            columns.push(Column::Tag);
+        }
+        columns
+    }
+
+    pub fn with_list(mut columns: Vec<Column>) -> Vec<Column> {
+        if !columns.contains(&Column::List) {
+            let idx = match columns.first() {
+                Some(Column::Id | Column::Number) => 1,
+                _ => 0,
+            };
+            columns.insert(idx, Column::List);
+        }
+        columns
+    }
+
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Number => "#",
+            Column::Title => "title",
+            Column::Status => "status",
+            Column::Prio => "prio",
+            Column::Due => "due",
+            Column::Tag => "tag",
+            Column::Created => "created",
+            Column::Updated => "updated",
+            Column::Age => "age",
+            Column::Urgency => "urgency",
+            Column::List => "list",
+        }
+    }
+
+    pub fn cell(&self, item: &TodoItem, metadata: &Metadata, ctx: &FormatContext) -> String {
+        match self {
+            Column::Id => ctx.id(&item.id),
+            Column::Number => metadata.number.to_string(),
+            Column::Title => parse_task(&item.task).0,
+            Column::Status => item.status.to_string(),
+            Column::Prio => ctx.prio(item.prio),
+            Column::Due => ctx.due(&item.due),
+            Column::Tag => item.tag.to_string(),
+            Column::Created => ctx.date(&metadata.created_at),
+            Column::Updated => ctx.date(&metadata.last_updated),
+            Column::Age => format_age(ctx.now.timestamp - metadata.created_at.timestamp),
+            Column::Urgency => format!("{:.1}", urgency(item, metadata, ctx)),
+            Column::List => metadata.list.clone(),
+        }
+    }
+}
+
+pub fn format_age(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        s if s < 60 * 60 => format!("{}m", s / 60),
+        s if s < DAY => format!("{}h", s / (60 * 60)),
+        s if s < 7 * DAY => format!("{}d", s / DAY),
+        s if s < 365 * DAY => format!("{}w", s / (7 * DAY)),
+        s => format!("{}y", s / (365 * DAY)),
+    }
+}
+
+// weights of the most important levels, the random one aside
+const PRIO_WEIGHTS: [f64; 3] = [6.0, 3.9, 1.8];
+
+// The priority weight, plus up to 12 points as the due date approaches (within two weeks, maxed
+// out once overdue), plus up to 2 points for age (one year).
+pub fn urgency(item: &TodoItem, metadata: &Metadata, ctx: &FormatContext) -> f64 {
+    if !item.status.is_active() {
+        return 0.0;
+    }
+    let now = ctx.now;
+    let random = Prio::random(&ctx.levels);
+    let prio = match item.prio {
+        Prio::Empty => 0.0,
+        prio if Some(prio) == random => 0.0,
+        prio => ctx
+            .levels
+            .iter()
+            .map(|level| Prio(Some(level.rank)))
+            .filter(|level| Some(*level) != random)
+            .position(|level| level == prio)
+            .and_then(|idx| PRIO_WEIGHTS.get(idx).copied())
+            .unwrap_or(0.0),
+    };
+    let due = if item.due == Datetime::epoch() {
+        0.0
+    } else {
+        let days_left = (item.due.timestamp - now.timestamp) as f64 / DAY as f64;
+        12.0 * (1.0 - days_left / 14.0).clamp(0.0, 1.0)
+    };
+    let age_days = (now.timestamp - metadata.created_at.timestamp).max(0) as f64 / DAY as f64;
+    let age = 2.0 * (age_days / 365.0).min(1.0);
+    prio + due + age
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    use crate::domain::todo::test_support::entry;
+    use crate::domain::{PrioLevel, Status};
+
+    fn ctx(now: Datetime) -> FormatContext {
+        FormatContext::new("%x", 7, Local.timestamp_opt(now.timestamp, 0).unwrap())
+    }
+
+    #[test]
+    fn should_lead_with_number_in_number_mode() {
+        assert_eq!(
+            Column::defaults(IdMode::Number, false, true),
+            vec![
+                Column::Number,
+                Column::Title,
+                Column::Status,
+                Column::Prio,
+                Column::Tag
+            ]
+        );
+    }
+
+    #[test]
+    fn should_add_list_after_leading_id() {
+        assert_eq!(
+            Column::with_list(vec![Column::Id, Column::Title]),
+            vec![Column::Id, Column::List, Column::Title]
+        );
+        assert_eq!(
+            Column::with_list(vec![Column::Title, Column::List]),
+            vec![Column::Title, Column::List]
+        );
+    }
+
+    #[test]
+    fn should_format_age_with_largest_unit() {
+        assert_eq!(format_age(90), "1m");
+        assert_eq!(format_age(5 * 60 * 60), "5h");
+        assert_eq!(format_age(3 * DAY), "3d");
+        assert_eq!(format_age(20 * DAY), "2w");
+        assert_eq!(format_age(800 * DAY), "2y");
+        assert_eq!(format_age(-10), "0m");
+    }
+
+    #[test]
+    fn should_rank_urgency_by_prio_due_and_age() {
+        let now = Datetime {
+            timestamp: 100 * DAY,
+        };
+        let (item, metadata) = entry("id").prio(Prio::P1).created(now).build();
+        assert_eq!(urgency(&item, &metadata, &ctx(now)), 6.0);
+        let due = Datetime {
+            timestamp: now.timestamp + 7 * DAY,
+        };
+        let (item, metadata) = entry("id").due(due).created(now).build();
+        assert_eq!(urgency(&item, &metadata, &ctx(now)), 6.0);
+        let overdue = Datetime {
+            timestamp: now.timestamp - DAY,
+        };
+        let (item, metadata) = entry("id")
+            .due(overdue)
+            .created(Datetime { timestamp: 0 })
+            .build();
+        assert!((urgency(&item, &metadata, &ctx(now)) - (12.0 + 200.0 / 365.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn should_have_no_urgency_when_closed() {
+        let now = Datetime { timestamp: DAY };
+        let (mut item, metadata) = entry("id").prio(Prio::P1).due(now).created(now).build();
+        item.status = Status::Closed;
+        assert_eq!(urgency(&item, &metadata, &ctx(now)), 0.0);
+    }
+
+    #[test]
+    fn should_weigh_configured_levels_by_order() {
+        let now = Datetime { timestamp: DAY };
+        let level = |name: &str, rank: i64| PrioLevel {
+            name: name.to_string(),
+            rank,
+            aliases: Vec::new(),
+            color: String::new(),
+        };
+        let mut ctx = ctx(now);
+        ctx.levels = vec![level("P0", 0), level("RND", 1), level("P2", 2)];
+        let (item, metadata) = entry("id").prio(Prio(Some(0))).created(now).build();
+        assert_eq!(urgency(&item, &metadata, &ctx), 6.0);
+        // the random level carries no weight and isn't counted
+        let (item, metadata) = entry("id").prio(Prio(Some(1))).created(now).build();
+        assert_eq!(urgency(&item, &metadata, &ctx), 0.0);
+        let (item, metadata) = entry("id").prio(Prio(Some(2))).created(now).build();
+        assert_eq!(urgency(&item, &metadata, &ctx), 3.9);
+    }
+}
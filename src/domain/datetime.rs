@@ -107,6 +107,23 @@ impl Datetime {
         }
     }
 
+    pub fn to_local(&self) -> DateTime<Local> {
+        DateTime::from_timestamp(self.timestamp, 0)
+            .unwrap_or_default()
+            .with_timezone(&Local)
+    }
+
+    pub fn to_iso_date(&self) -> Option<String> {
+        if *self == Datetime::epoch() {
+            return None;
+        }
+        Some(self.to_local().format("%Y-%m-%d").to_string())
+    }
+
+    pub fn to_rfc3339(&self) -> String {
+        self.to_local().to_rfc3339()
+    }
+
     pub fn next_weekday(date: DateTime<Local>, next_weekday: Weekday) -> NaiveDate {
         let today = date.date_naive();
         let weekday = today.weekday();
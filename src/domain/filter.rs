@@ -0,0 +1,653 @@
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, NaiveTime, TimeZone, Weekday};
+use clap::ValueEnum;
+use std::fmt;
+
+use crate::domain::{Datetime, Prio, PrioLevel, Status, StatusFilter, Tag};
+
+// e.g. `prio:p1,p2 and (tag:backend or tag:infra)`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Condition(Condition),
+}
+
+// a list of values matches any of them
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Status(Vec<Status>),
+    // `Prio::Empty` stands for tasks without priority
+    Prio(Comparison, Vec<Prio>),
+    // an empty tag stands for tasks without tag
+    Tag(Vec<Tag>),
+    // `None` stands for tasks without due date
+    Date(DateField, Comparison, Vec<Option<NaiveDate>>),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    Due,
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+const FIELDS: &str = "status, prio, tag, due, created, updated, text";
+
+pub struct FilterContext<'a> {
+    pub today: NaiveDate,
+    pub date_input_format: &'a str,
+    pub levels: &'a [PrioLevel],
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FilterParseError {
+    pub message: String,
+    // character offset into the input
+    pub position: usize,
+    pub input: String,
+}
+
+impl fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "  {}", self.input)?;
+        write!(f, "  {}^", " ".repeat(self.position))
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
+impl FilterExpr {
+    pub fn parse(input: &str, ctx: &FilterContext) -> Result<FilterExpr, FilterParseError> {
+        let error = |message: String, position: usize| FilterParseError {
+            message,
+            position,
+            input: input.to_string(),
+        };
+        let tokens = tokenize(input).map_err(|(message, position)| error(message, position))?;
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: input.chars().count(),
+            ctx,
+        };
+        let expr = parser
+            .expr()
+            .map_err(|(message, position)| error(message, position))?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(error(
+                format!("unexpected {}", token.kind.describe()),
+                token.position,
+            )),
+        }
+    }
+
+    pub fn mentions_status(&self) -> bool {
+        match self {
+            FilterExpr::And(left, right) | FilterExpr::Or(left, right) => {
+                left.mentions_status() || right.mentions_status()
+            }
+            FilterExpr::Not(expr) => expr.mentions_status(),
+            FilterExpr::Condition(condition) => matches!(condition, Condition::Status(_)),
+        }
+    }
+}
+
+pub fn start_of_day(day: NaiveDate) -> Datetime {
+    let timestamp = Local
+        .from_local_datetime(&day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|datetime| datetime.timestamp())
+        .unwrap_or_default();
+    Datetime { timestamp }
+}
+
+// --------- Lexer --------- //
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term {
+        field: String,
+        comparison: Comparison,
+        // each value with its position
+        values: Vec<(String, usize)>,
+    },
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::LParen => "'('".to_string(),
+            TokenKind::RParen => "')'".to_string(),
+            TokenKind::And => "'and'".to_string(),
+            TokenKind::Or => "'or'".to_string(),
+            TokenKind::Not => "'not'".to_string(),
+            TokenKind::Term { field, .. } => format!("condition on '{field}'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+type Positioned<T> = Result<T, (String, usize)>;
+
+fn tokenize(input: &str) -> Positioned<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let position = idx;
+        match chars[idx] {
+            c if c.is_whitespace() => idx += 1,
+            '(' => {
+                tokens.push(Token {
+                    kind: TokenKind::LParen,
+                    position,
+                });
+                idx += 1;
+            }
+            ')' => {
+                tokens.push(Token {
+                    kind: TokenKind::RParen,
+                    position,
+                });
+                idx += 1;
+            }
+            c if c.is_alphabetic() => {
+                while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '_') {
+                    idx += 1;
+                }
+                let word: String = chars[position..idx].iter().collect();
+                let kind = match word.to_lowercase().as_str() {
+                    "and" => TokenKind::And,
+                    "or" => TokenKind::Or,
+                    "not" => TokenKind::Not,
+                    _ => {
+                        let comparison = comparison(&chars, &mut idx).ok_or((
+                            format!("expected ':', '<' or '>' after '{word}', as in {word}:value"),
+                            idx,
+                        ))?;
+                        TokenKind::Term {
+                            field: word.to_lowercase(),
+                            comparison,
+                            values: values(&chars, &mut idx)?,
+                        }
+                    }
+                };
+                tokens.push(Token { kind, position });
+            }
+            c => return Err((format!("unexpected '{c}'"), position)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn comparison(chars: &[char], idx: &mut usize) -> Option<Comparison> {
+    let next = chars.get(*idx + 1) == Some(&'=');
+    let (comparison, len) = match chars.get(*idx)? {
+        ':' | '=' => (Comparison::Equal, 1),
+        '<' if next => (Comparison::LessOrEqual, 2),
+        '<' => (Comparison::Less, 1),
+        '>' if next => (Comparison::GreaterOrEqual, 2),
+        '>' => (Comparison::Greater, 1),
+        _ => return None,
+    };
+    *idx += len;
+    Some(comparison)
+}
+
+// Comma separated values, each bare or in double quotes
+fn values(chars: &[char], idx: &mut usize) -> Positioned<Vec<(String, usize)>> {
+    let mut values = Vec::new();
+    loop {
+        let position = *idx;
+        let mut value = String::new();
+        if chars.get(*idx) == Some(&'"') {
+            *idx += 1;
+            loop {
+                match chars.get(*idx) {
+                    Some('"') => break,
+                    Some(c) => value.push(*c),
+                    None => return Err(("unterminated quote".to_string(), position)),
+                }
+                *idx += 1;
+            }
+            *idx += 1;
+        } else {
+            while let Some(c) = chars.get(*idx) {
+                if c.is_whitespace() || ['(', ')', ','].contains(c) {
+                    break;
+                }
+                value.push(*c);
+                *idx += 1;
+            }
+        }
+        if value.is_empty() {
+            return Err(("expected a value".to_string(), position));
+        }
+        values.push((value, position));
+        if chars.get(*idx) != Some(&',') {
+            return Ok(values);
+        }
+        *idx += 1;
+    }
+}
+
+// --------- Parser --------- //
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    next: usize,
+    // position of the end of input
+    end: usize,
+    ctx: &'a FilterContext<'a>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    // expr := and ('or' and)*
+    fn expr(&mut self) -> Positioned<FilterExpr> {
+        let mut expr = self.and()?;
+        while self.peek().map(|token| &token.kind) == Some(&TokenKind::Or) {
+            self.advance();
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    // and := unary ('and'? unary)*, adjacent conditions are combined with 'and'
+    fn and(&mut self) -> Positioned<FilterExpr> {
+        let mut expr = self.unary()?;
+        loop {
+            match self.peek().map(|token| &token.kind) {
+                Some(TokenKind::And) => {
+                    self.advance();
+                }
+                Some(TokenKind::Not | TokenKind::LParen | TokenKind::Term { .. }) => {}
+                _ => return Ok(expr),
+            }
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    // unary := 'not' unary | '(' expr ')' | term
+    fn unary(&mut self) -> Positioned<FilterExpr> {
+        let Some(token) = self.advance() else {
+            return Err(("expected a condition".to_string(), self.end));
+        };
+        match token.kind {
+            TokenKind::Not => Ok(FilterExpr::Not(Box::new(self.unary()?))),
+            TokenKind::LParen => {
+                let expr = self.expr()?;
+                match self.advance() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => Ok(expr),
+                    Some(token) => Err((
+                        format!("expected ')', found {}", token.kind.describe()),
+                        token.position,
+                    )),
+                    None => Err((
+                        format!("expected ')' to close the '(' at {}", token.position),
+                        self.end,
+                    )),
+                }
+            }
+            TokenKind::Term {
+                field,
+                comparison,
+                values,
+            } => Ok(FilterExpr::Condition(self.condition(
+                &field,
+                comparison,
+                values,
+                token.position,
+            )?)),
+            kind => Err((
+                format!("expected a condition, found {}", kind.describe()),
+                token.position,
+            )),
+        }
+    }
+
+    fn condition(
+        &self,
+        field: &str,
+        comparison: Comparison,
+        values: Vec<(String, usize)>,
+        position: usize,
+    ) -> Positioned<Condition> {
+        let op_position = position + field.chars().count();
+        let equality_only = || {
+            if comparison == Comparison::Equal {
+                Ok(())
+            } else {
+                Err((
+                    format!("'{field}' can only be matched with ':'"),
+                    op_position,
+                ))
+            }
+        };
+        if comparison != Comparison::Equal && values.len() > 1 {
+            return Err(("comparisons take a single value".to_string(), values[1].1));
+        }
+        match field {
+            "status" => {
+                equality_only()?;
+                let mut statuses = Vec::new();
+                for (value, position) in values {
+                    let filter = StatusFilter::from_str(&value, true).map_err(|_| {
+                        (
+                            format!(
+                                "unknown status '{value}', expected one of: open, in-progress, blocked, done, cancelled, do, all"
+                            ),
+                            position,
+                        )
+                    })?;
+                    statuses.extend(
+                        Status::value_variants()
+                            .iter()
+                            .filter(|status| filter.matches(**status)),
+                    );
+                }
+                Ok(Condition::Status(statuses))
+            }
+            "prio" => {
+                let prios = values
+                    .into_iter()
+                    .map(|(value, position)| {
+                        Prio::parse_with(self.ctx.levels, &value)
+                            .map_err(|err| (err.to_string(), position))
+                    })
+                    .collect::<Positioned<Vec<_>>>()?;
+                if comparison != Comparison::Equal && prios.contains(&Prio::Empty) {
+                    return Err((
+                        "'empty' can only be matched with ':'".to_string(),
+                        op_position,
+                    ));
+                }
+                Ok(Condition::Prio(comparison, prios))
+            }
+            "tag" => {
+                equality_only()?;
+                let tags = values
+                    .into_iter()
+                    .map(|(value, _)| match value.trim_start_matches('#') {
+                        "empty" => Tag(String::new()),
+                        tag => Tag(tag.to_string()),
+                    })
+                    .collect();
+                Ok(Condition::Tag(tags))
+            }
+            "due" | "created" | "updated" => {
+                let field = match field {
+                    "due" => DateField::Due,
+                    "created" => DateField::Created,
+                    _ => DateField::Updated,
+                };
+                let days = values
+                    .into_iter()
+                    .map(|(value, position)| match value.as_str() {
+                        "empty" if field == DateField::Due && comparison == Comparison::Equal => {
+                            Ok(None)
+                        }
+                        _ => self.day(&value).map(Some).map_err(|msg| (msg, position)),
+                    })
+                    .collect::<Positioned<Vec<_>>>()?;
+                Ok(Condition::Date(field, comparison, days))
+            }
+            "text" => {
+                equality_only()?;
+                let text = values
+                    .into_iter()
+                    .map(|(value, _)| value)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                Ok(Condition::Text(text))
+            }
+            _ => Err((
+                format!("unknown field '{field}', expected one of: {FIELDS}"),
+                position,
+            )),
+        }
+    }
+
+    // `today`, `eow`, `fri`, `-7d`, `+2w`, `1m` or a date in the input format
+    fn day(&self, value: &str) -> Result<NaiveDate, String> {
+        let today = self.ctx.today;
+        let lower = value.to_lowercase();
+        match lower.as_str() {
+            "today" | "eod" => return Ok(today),
+            "tomorrow" => return Ok(today + Duration::days(1)),
+            "yesterday" => return Ok(today - Duration::days(1)),
+            "eow" => return Ok(next_weekday(today, Weekday::Fri)),
+            "eom" => {
+                let first = today.with_day0(0).unwrap_or(today);
+                return Ok(first + Months::new(1) - Duration::days(1));
+            }
+            _ => {}
+        }
+        if let Ok(weekday) = lower.parse::<Weekday>() {
+            return Ok(next_weekday(today, weekday));
+        }
+        // ISO dates are unambiguous whatever the input format
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            return Ok(date);
+        }
+        if let Some(offset) = relative_offset(&lower) {
+            return today
+                .checked_add_signed(offset)
+                .ok_or(format!("'{value}' is out of range"));
+        }
+        Datetime::parse(value, self.ctx.date_input_format.to_string())
+            .map(|datetime| datetime.to_local().date_naive())
+            .map_err(|_| {
+                format!("invalid date '{value}', expected e.g. today, eow, fri, -7d, +2w or a date")
+            })
+    }
+}
+
+// Like `Datetime::parse`, today counts as the next occurrence of its own weekday.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days =
+        (weekday.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64 + 7)
+            % 7;
+    today + Duration::days(days)
+}
+
+// `-7d`, `+2w`, `3m` (30 days per month)
+fn relative_offset(value: &str) -> Option<Duration> {
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let unit = rest.chars().last()?;
+    let count: i64 = rest[..rest.len() - unit.len_utf8()].parse().ok()?;
+    let days = match unit {
+        'd' => count,
+        'w' => count * 7,
+        'm' => count * 30,
+        _ => return None,
+    };
+    Some(Duration::days(sign * days))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a Wednesday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 11).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<FilterExpr, FilterParseError> {
+        let levels = PrioLevel::defaults();
+        let ctx = FilterContext {
+            today: today(),
+            date_input_format: "DMY",
+            levels: &levels,
+        };
+        FilterExpr::parse(input, &ctx)
+    }
+
+    fn condition(condition: Condition) -> Box<FilterExpr> {
+        Box::new(FilterExpr::Condition(condition))
+    }
+
+    fn error_at(input: &str) -> (String, usize) {
+        let err = parse(input).unwrap_err();
+        (err.message, err.position)
+    }
+
+    #[test]
+    fn should_parse_expression_with_precedence() {
+        let expr = parse("prio:p1,p2 and (tag:backend or tag:infra) and not status:done").unwrap();
+        let tags = FilterExpr::Or(
+            condition(Condition::Tag(vec![Tag("backend".to_string())])),
+            condition(Condition::Tag(vec![Tag("infra".to_string())])),
+        );
+        let expected = FilterExpr::And(
+            Box::new(FilterExpr::And(
+                condition(Condition::Prio(Comparison::Equal, vec![Prio::P1, Prio::P2])),
+                Box::new(tags),
+            )),
+            Box::new(FilterExpr::Not(condition(Condition::Status(vec![
+                Status::Closed,
+            ])))),
+        );
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn should_bind_and_tighter_than_or() {
+        let expr = parse("tag:a or tag:b tag:c").unwrap();
+        let FilterExpr::Or(_, right) = expr else {
+            panic!("expected 'or' at the top, got {expr:?}");
+        };
+        assert!(matches!(*right, FilterExpr::And(_, _)));
+    }
+
+    #[test]
+    fn should_resolve_dates() {
+        let day = |input: &str| match parse(input).unwrap() {
+            FilterExpr::Condition(Condition::Date(_, _, days)) => days[0],
+            expr => panic!("expected a date condition, got {expr:?}"),
+        };
+        let date = |d: u32| NaiveDate::from_ymd_opt(2025, 6, d);
+        assert_eq!(day("due<eow"), date(13));
+        assert_eq!(day("created>-7d"), date(4));
+        assert_eq!(day("due:+1w"), date(18));
+        assert_eq!(day("due:today"), date(11));
+        assert_eq!(day("due:eom"), date(30));
+        assert_eq!(day("due:mon"), date(16));
+        assert_eq!(day("updated>=2025-06-01"), date(1));
+        assert_eq!(day("due:empty"), None);
+    }
+
+    #[test]
+    fn should_expand_status_groups() {
+        assert_eq!(
+            parse("status:do").unwrap(),
+            FilterExpr::Condition(Condition::Status(vec![
+                Status::Open,
+                Status::InProgress,
+                Status::Blocked,
+            ]))
+        );
+    }
+
+    #[test]
+    fn should_point_at_errors() {
+        assert_eq!(
+            error_at("prio:p1 and (tag:x"),
+            ("expected ')' to close the '(' at 12".to_string(), 18)
+        );
+        assert_eq!(
+            error_at("size:3"),
+            (
+                format!("unknown field 'size', expected one of: {FIELDS}"),
+                0
+            )
+        );
+        assert_eq!(
+            error_at("tag:x and prio:p9").0,
+            "unknown priority 'p9', expected one of: P1, P2, P3, RND, empty"
+        );
+        assert_eq!(error_at("tag:x and prio:p9").1, 15);
+        assert_eq!(
+            error_at("status<done"),
+            ("'status' can only be matched with ':'".to_string(), 6)
+        );
+        assert_eq!(
+            error_at("due<eow,fri"),
+            ("comparisons take a single value".to_string(), 8)
+        );
+        assert_eq!(
+            error_at("tag:x or"),
+            ("expected a condition".to_string(), 8)
+        );
+        assert_eq!(
+            error_at("backend"),
+            (
+                "expected ':', '<' or '>' after 'backend', as in backend:value".to_string(),
+                7
+            )
+        );
+        assert_eq!(error_at("tag:x )"), ("unexpected ')'".to_string(), 6));
+    }
+
+    #[test]
+    fn should_render_error_with_caret() {
+        let err = parse("tag:x or").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected a condition\n  tag:x or\n          ^"
+        );
+    }
+
+    #[test]
+    fn should_parse_quoted_values() {
+        assert_eq!(
+            parse(r#"text:"buy milk, eggs""#).unwrap(),
+            FilterExpr::Condition(Condition::Text("buy milk, eggs".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_tell_whether_status_is_mentioned() {
+        assert!(parse("tag:a or not status:done").unwrap().mentions_status());
+        assert!(!parse("tag:a").unwrap().mentions_status());
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::prelude::*;
+use std::collections::HashMap;
+
+use crate::application::config::Config;
+use crate::domain::{unique_prefix_lengths, Datetime, Prio, PrioLevel};
+
+// Resolved once per command, so that formatting a cell neither reads the config nor asks for
+// the current time again.
+#[derive(Debug, Clone)]
+pub struct FormatContext {
+    pub date_format: String,
+    pub due_horizon: i64,
+    pub now: Datetime,
+    pub id_length: usize,
+    // ordered by rank
+    pub levels: Vec<PrioLevel>,
+    // shortest unique prefix per id of the list, see `with_ids`
+    id_prefixes: HashMap<String, usize>,
+    today: NaiveDate,
+    // upcoming Mon..Fri, labelled by name (Fri as EOW)
+    this_week: [NaiveDate; 5],
+}
+
+const WEEKDAY_LABELS: [&str; 5] = ["Mon", "Tue", "Wed", "Thu", "EOW"];
+
+impl FormatContext {
+    pub fn new(date_format: &str, due_horizon: i64, now: DateTime<Local>) -> Self {
+        let weekdays = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ];
+        Self {
+            date_format: date_format.to_string(),
+            due_horizon,
+            now: Datetime {
+                timestamp: now.timestamp(),
+            },
+            id_length: 0,
+            levels: PrioLevel::defaults(),
+            id_prefixes: HashMap::new(),
+            today: now.date_naive(),
+            this_week: weekdays.map(|weekday| Datetime::next_weekday(now, weekday)),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let date_format = match config.style.due_date_display_format.as_str() {
+            "" => "%x",
+            format => format,
+        };
+        let mut levels = config.priorities.clone();
+        levels.sort_by_key(|level| level.rank);
+        Self {
+            id_length: config.style.id_length,
+            levels,
+            ..Self::new(date_format, config.style.due_horizon, Local::now())
+        }
+    }
+
+    // `ids` are all ids of the list, not only the displayed ones
+    pub fn with_ids(self, ids: &[String]) -> Self {
+        Self {
+            id_prefixes: unique_prefix_lengths(ids, self.id_length),
+            ..self
+        }
+    }
+
+    pub fn id(&self, id: &str) -> String {
+        let length = self.id_prefixes.get(id).copied().unwrap_or(self.id_length);
+        id.chars().take(length).collect()
+    }
+
+    pub fn prio(&self, prio: Prio) -> String {
+        prio.name(&self.levels)
+    }
+
+    pub fn prio_level(&self, prio: Prio) -> Option<&PrioLevel> {
+        prio.level(&self.levels)
+    }
+
+    pub fn date(&self, date: &Datetime) -> String {
+        if *date == Datetime::epoch() {
+            return String::new();
+        }
+        let local = date.to_local();
+        let day = local.date_naive();
+        match (day - self.today).num_days() {
+            -1 => "Yesterday".to_string(),
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            _ => match self.this_week.iter().position(|weekday| *weekday == day) {
+                Some(idx) => WEEKDAY_LABELS[idx].to_string(),
+                None => local.format(&self.date_format).to_string(),
+            },
+        }
+    }
+
+    pub fn days_from_today(&self, date: &Datetime) -> i64 {
+        (date.to_local().date_naive() - self.today).num_days()
+    }
+
+    pub fn due(&self, due: &Datetime) -> String {
+        if *due == Datetime::epoch() {
+            return String::new();
+        }
+        match self.days_from_today(due) {
+            days if days < 0 => format!("{}d overdue", -days),
+            0 => "Today".to_string(),
+            days if days <= self.due_horizon => format!("in {days}d"),
+            _ => self.date(due),
+        }
+    }
+
+    pub fn days_until_end_of_week(&self) -> i64 {
+        6 - self.today.weekday().num_days_from_monday() as i64
+    }
+
+    pub fn is_relative_due(&self, due: &Datetime) -> bool {
+        *due != Datetime::epoch() && self.days_from_today(due) <= self.due_horizon
+    }
+
+    pub fn is_overdue(&self, due: &Datetime) -> bool {
+        *due != Datetime::epoch() && self.days_from_today(due) < 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Duration;
+
+    // a Wednesday
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 6, 11, 12, 0, 0).unwrap()
+    }
+
+    fn in_days(days: i64) -> Datetime {
+        Datetime {
+            timestamp: (now() + Duration::days(days)).timestamp(),
+        }
+    }
+
+    #[test]
+    fn should_label_days_around_today() {
+        let ctx = FormatContext::new("%d.%m.%Y", 0, now());
+        assert_eq!(ctx.date(&in_days(-1)), "Yesterday");
+        assert_eq!(ctx.date(&in_days(0)), "Today");
+        assert_eq!(ctx.date(&in_days(1)), "Tomorrow");
+        assert_eq!(ctx.date(&in_days(2)), "EOW");
+        assert_eq!(ctx.date(&in_days(5)), "Mon");
+        assert_eq!(ctx.date(&in_days(10)), "21.06.2025");
+        assert_eq!(ctx.date(&Datetime::epoch()), "");
+    }
+
+    #[test]
+    fn should_display_due_dates_relative_to_today() {
+        let ctx = FormatContext::new("%d.%m.%Y", 7, now());
+        assert_eq!(ctx.due(&in_days(-3)), "3d overdue");
+        assert_eq!(ctx.due(&in_days(0)), "Today");
+        assert_eq!(ctx.due(&in_days(5)), "in 5d");
+        assert_eq!(ctx.due(&in_days(10)), "21.06.2025");
+        assert_eq!(ctx.due(&Datetime::epoch()), "");
+    }
+
+    #[test]
+    fn should_abbreviate_ids_to_unique_prefixes() {
+        let ctx = FormatContext {
+            id_length: 4,
+            ..FormatContext::new("%x", 7, now())
+        };
+        let ids = vec!["01JA0000".to_string(), "01JA1111".to_string()];
+        let ctx = ctx.with_ids(&ids);
+        assert_eq!(ctx.id("01JA0000"), "01JA0");
+        assert_eq!(ctx.id("01JB2222"), "01JB");
+    }
+
+    #[test]
+    fn should_only_be_relative_within_horizon() {
+        let ctx = FormatContext::new("%x", 7, now());
+        assert!(ctx.is_relative_due(&in_days(-3)));
+        assert!(ctx.is_overdue(&in_days(-3)));
+        assert!(ctx.is_relative_due(&in_days(5)));
+        assert!(!ctx.is_relative_due(&in_days(8)));
+        assert!(!ctx.is_relative_due(&Datetime::epoch()));
+        assert!(!ctx.is_overdue(&Datetime::epoch()));
+    }
+}
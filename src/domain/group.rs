@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Datetime, FormatContext, Metadata, Prio, Status, TodoItem};
+
+#[derive(clap::ValueEnum, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Tag,
+    Prio,
+    Due,
+    List,
+    Status,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub title: String,
+    pub entries: Vec<(TodoItem, Metadata)>,
+}
+
+impl Group {
+    pub fn header(&self) -> String {
+        let count = |matches: fn(Status) -> bool| {
+            self.entries
+                .iter()
+                .filter(|(item, _)| matches(item.status))
+                .count()
+        };
+        let counts: Vec<String> = [
+            (count(|status| status.is_active()), "open"),
+            (count(|status| status == Status::Closed), "done"),
+            (count(|status| status == Status::Cancelled), "cancelled"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect();
+        format!("{} ({})", self.title, counts.join(", "))
+    }
+}
+
+// Entries keep their order within a group, so they are expected to be sorted already.
+pub fn group_entries(
+    entries: Vec<(TodoItem, Metadata)>,
+    group_by: GroupBy,
+    ctx: &FormatContext,
+) -> Vec<Group> {
+    let mut keyed: Vec<((i64, String), Group)> = Vec::new();
+    for entry in entries {
+        let key = group_key(&entry, group_by, ctx);
+        match keyed.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, group)) => group.entries.push(entry),
+            None => {
+                let title = key.1.clone();
+                keyed.push((
+                    key,
+                    Group {
+                        title,
+                        entries: vec![entry],
+                    },
+                ));
+            }
+        }
+    }
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    keyed.into_iter().map(|(_, group)| group).collect()
+}
+
+// (rank, title): groups sort by rank first, then alphabetically by title
+fn group_key(
+    (item, metadata): &(TodoItem, Metadata),
+    group_by: GroupBy,
+    ctx: &FormatContext,
+) -> (i64, String) {
+    match group_by {
+        GroupBy::Tag if item.tag.0.is_empty() => (1, "No tag".to_string()),
+        GroupBy::Tag => (0, item.tag.to_string()),
+        GroupBy::Prio => match item.prio {
+            Prio(Some(rank)) => (rank, ctx.prio(item.prio)),
+            Prio::Empty => (i64::MAX, "No prio".to_string()),
+        },
+        GroupBy::Due if ctx.is_overdue(&item.due) => (0, "Overdue".to_string()),
+        GroupBy::Due => match ctx.days_from_today(&item.due) {
+            _ if item.due == Datetime::epoch() => (4, "No due date".to_string()),
+            0 => (1, "Today".to_string()),
+            days if days <= ctx.days_until_end_of_week() => (2, "This week".to_string()),
+            _ => (3, "Later".to_string()),
+        },
+        GroupBy::List => (0, metadata.list.clone()),
+        GroupBy::Status => match item.status {
+            Status::InProgress => (0, "In progress".to_string()),
+            Status::Open => (1, "Open".to_string()),
+            Status::Blocked => (2, "Blocked".to_string()),
+            Status::Closed => (3, "Done".to_string()),
+            Status::Cancelled => (4, "Cancelled".to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Duration, Local, TimeZone};
+
+    use crate::domain::todo::test_support::entry;
+
+    fn ctx() -> FormatContext {
+        // a Wednesday
+        FormatContext::new(
+            "%x",
+            7,
+            Local.with_ymd_and_hms(2025, 6, 11, 12, 0, 0).unwrap(),
+        )
+    }
+
+    fn in_days(days: i64) -> Datetime {
+        Datetime {
+            timestamp: ctx().now.timestamp + Duration::days(days).num_seconds(),
+        }
+    }
+
+    #[test]
+    fn should_group_by_tag_keeping_order_and_untagged_last() {
+        let entries = vec![
+            entry("1").build(),
+            entry("2").tag("web").build(),
+            entry("3").tag("api").status(Status::Closed).build(),
+            entry("4").tag("web").status(Status::Closed).build(),
+        ];
+        let groups = group_entries(entries, GroupBy::Tag, &ctx());
+        let headers: Vec<String> = groups.iter().map(Group::header).collect();
+        assert_eq!(
+            headers,
+            vec!["#api (1 done)", "#web (1 open, 1 done)", "No tag (1 open)"]
+        );
+        let ids: Vec<&str> = groups[1]
+            .entries
+            .iter()
+            .map(|(item, _)| item.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2", "4"]);
+    }
+
+    #[test]
+    fn should_group_by_list_of_each_entry() {
+        let mut entries = vec![entry("1").build(), entry("2").build()];
+        entries[0].1.list = "work".to_string();
+        let groups = group_entries(entries, GroupBy::List, &ctx());
+        let titles: Vec<&str> = groups.iter().map(|group| group.title.as_str()).collect();
+        assert_eq!(titles, vec!["todo", "work"]);
+    }
+
+    #[test]
+    fn should_group_by_due_buckets_and_omit_empty_ones() {
+        let entries = vec![
+            entry("1").due(in_days(30)).build(),
+            entry("2").due(in_days(-2)).build(),
+            entry("3").due(in_days(3)).build(),
+            entry("4").due(in_days(5)).build(),
+        ];
+        let groups = group_entries(entries, GroupBy::Due, &ctx());
+        let titles: Vec<&str> = groups.iter().map(|group| group.title.as_str()).collect();
+        assert_eq!(titles, vec!["Overdue", "This week", "Later"]);
+        assert_eq!(groups[2].entries.len(), 2);
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+
+use crate::domain::TodoItem;
+use crate::util::parse_task;
+
+#[derive(Error, Debug)]
+pub struct AmbiguousIdError {
+    pub reference: String,
+    pub candidates: Vec<TodoItem>,
+}
+
+impl fmt::Display for AmbiguousIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "✘ Ambiguous prefix '{}' matches {} tasks:",
+            self.reference,
+            self.candidates.len()
+        )?;
+        for item in &self.candidates {
+            write!(f, "\n  {}  {}", item.id, parse_task(&item.task).0)?;
+        }
+        Ok(())
+    }
+}
+
+// Prefixes are compared case-insensitively, the way `resolve_id` matches them.
+pub fn unique_prefix_lengths(ids: &[String], min_length: usize) -> HashMap<String, usize> {
+    let mut sorted: Vec<(String, &String)> = ids.iter().map(|id| (id.to_lowercase(), id)).collect();
+    sorted.sort();
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(idx, (lower, id))| {
+            let shared_with = |other: Option<&(String, &String)>| {
+                other.map_or(0, |(other, _)| common_prefix_length(lower, other))
+            };
+            let previous = idx.checked_sub(1).and_then(|prev| sorted.get(prev));
+            let shared = shared_with(previous).max(shared_with(sorted.get(idx + 1)));
+            let length = (shared + 1).max(min_length).min(id.chars().count());
+            (id.to_string(), length)
+        })
+        .collect()
+}
+
+fn common_prefix_length(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn should_extend_prefixes_until_unique() {
+        let lengths = unique_prefix_lengths(&ids(&["01JABCDE", "01JABXYZ", "01KQRSTU"]), 3);
+        assert_eq!(lengths["01JABCDE"], 6);
+        assert_eq!(lengths["01JABXYZ"], 6);
+        assert_eq!(lengths["01KQRSTU"], 3);
+    }
+
+    #[test]
+    fn should_compare_case_insensitively_and_cap_at_full_length() {
+        let lengths = unique_prefix_lengths(&ids(&["abc123", "ABC124", "abc"]), 2);
+        assert_eq!(lengths["abc123"], 6);
+        assert_eq!(lengths["ABC124"], 6);
+        assert_eq!(lengths["abc"], 3);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::domain::Datetime;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Metadata {
     pub created_at: Datetime,
     pub last_updated: Datetime,
+    pub number: i64,
+    pub list: String,
 }
@@ -1,17 +1,39 @@
+pub mod board;
+pub mod column;
 pub mod datetime;
+pub mod filter;
+pub mod format;
+pub mod group;
+pub mod id;
 pub mod metadata;
+pub mod output;
 pub mod prio;
 pub mod repository;
+pub mod sort;
 pub mod status;
+pub mod summary;
 pub mod table;
 pub mod tag;
+pub mod template;
 pub mod todo;
+pub mod view;
 
+pub use board::*;
+pub use column::*;
 pub use datetime::*;
+pub use filter::*;
+pub use format::*;
+pub use group::*;
+pub use id::*;
 pub use metadata::*;
+pub use output::*;
 pub use prio::*;
 pub use repository::*;
+pub use sort::*;
 pub use status::*;
+pub use summary::*;
 pub use table::*;
 pub use tag::*;
+pub use template::*;
 pub use todo::*;
+pub use view::*;
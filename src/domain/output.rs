@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Jsonl,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn is_table(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+
+    pub fn is_json(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Jsonl)
+    }
+
+    pub fn render_records<T: Serialize>(&self, records: &[T]) -> Result<String> {
+        let values = records
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<Value>, _>>()?;
+        match self {
+            OutputFormat::Json => Ok(serde_json::to_string_pretty(&values)?),
+            OutputFormat::Jsonl => Ok(values
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")),
+            OutputFormat::Table | OutputFormat::Tsv => render_tsv(&values),
+        }
+    }
+}
+
+fn render_tsv(values: &[Value]) -> Result<String> {
+    let Some(first) = values.first() else {
+        return Ok(String::new());
+    };
+    let header: Vec<String> = first
+        .as_object()
+        .ok_or(anyhow!("✘ Only records can be rendered as TSV"))?
+        .keys()
+        .cloned()
+        .collect();
+    let mut lines = vec![header.join("\t")];
+    for value in values {
+        let row: Vec<String> = header
+            .iter()
+            .map(|key| tsv_field(value.get(key).unwrap_or(&Value::Null)))
+            .collect();
+        lines.push(row.join("\t"));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn tsv_field(value: &Value) -> String {
+    let raw = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    raw.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        name: String,
+        count: Option<u32>,
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                name: "first\tline\nsecond".to_string(),
+                count: Some(1),
+            },
+            Record {
+                name: "other".to_string(),
+                count: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn should_render_jsonl_one_record_per_line() -> Result<()> {
+        let rendered = OutputFormat::Jsonl.render_records(&records())?;
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], r#"{"name":"other","count":null}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_tsv_with_header_and_escapes() -> Result<()> {
+        let rendered = OutputFormat::Tsv.render_records(&records())?;
+        let expected = "name\tcount\nfirst\\tline\\nsecond\t1\nother\t";
+        assert_eq!(rendered, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_empty_json_array() -> Result<()> {
+        let empty: Vec<Record> = Vec::new();
+        assert_eq!(OutputFormat::Json.render_records(&empty)?, "[]");
+        assert_eq!(OutputFormat::Tsv.render_records(&empty)?, "");
+        Ok(())
+    }
+}
@@ -1,28 +1,226 @@
-use clap::ValueEnum;
-use std::fmt;
-
-#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Default, ValueEnum)]
-pub enum Prio {
-    #[value(aliases = ["P1","p1","1","high"])]
-    P1,
-    #[value(aliases = ["P2","p2","2","mid"])]
-    P2,
-    #[value(aliases = ["P3","p3","3","low"])]
-    P3,
-    #[value(aliases = ["RND", "rnd"])]
-    RND,
-    #[default]
-    Empty,
-}
-
-impl fmt::Display for Prio {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Prio::P1 => write!(f, "P1"),
-            Prio::P2 => write!(f, "P2"),
-            Prio::P3 => write!(f, "P3"),
-            Prio::RND => write!(f, "RND"),
-            Prio::Empty => write!(f, ""),
+use serde::Deserialize;
+use std::cmp::Ordering;
+use thiserror::Error;
+
+// The rank of a level, `Empty` (no priority) sorts after all of them.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Prio(pub Option<i64>);
+
+#[allow(non_upper_case_globals)]
+impl Prio {
+    pub const Empty: Prio = Prio(None);
+    pub const P1: Prio = Prio(Some(1));
+    pub const P2: Prio = Prio(Some(2));
+    pub const P3: Prio = Prio(Some(3));
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PrioLevel {
+    pub name: String,
+    // stored with each task, so changing it detaches the level from existing tasks
+    pub rank: i64,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub color: String,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PrioLevelError {
+    #[error("✘ Priority name or alias '{0}' is declared twice")]
+    DuplicateName(String),
+    #[error("✘ Priority rank {0} is declared twice")]
+    DuplicateRank(i64),
+    #[error("✘ 'empty' is reserved for tasks without priority")]
+    Reserved,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("unknown priority '{value}', expected one of: {expected}")]
+pub struct PrioParseError {
+    value: String,
+    expected: String,
+}
+
+impl PrioLevel {
+    fn new(name: &str, rank: i64, aliases: &[&str], color: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            rank,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            color: color.to_string(),
+        }
+    }
+
+    pub fn defaults() -> Vec<PrioLevel> {
+        vec![
+            PrioLevel::new("P1", 1, &["1", "high"], "red"),
+            PrioLevel::new("P2", 2, &["2", "mid"], "yellow"),
+            PrioLevel::new("P3", 3, &["3", "low"], "green"),
+            PrioLevel::new("RND", 99, &[], "purple"),
+        ]
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        self.name.eq_ignore_ascii_case(value)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(value))
+    }
+}
+
+pub fn validate_prio_levels(levels: &[PrioLevel]) -> Result<(), PrioLevelError> {
+    let mut names: Vec<String> = Vec::new();
+    let mut ranks: Vec<i64> = Vec::new();
+    for level in levels {
+        if ranks.contains(&level.rank) {
+            return Err(PrioLevelError::DuplicateRank(level.rank));
+        }
+        ranks.push(level.rank);
+        for name in std::iter::once(&level.name).chain(&level.aliases) {
+            let name = name.to_lowercase();
+            if name == "empty" {
+                return Err(PrioLevelError::Reserved);
+            }
+            if names.contains(&name) {
+                return Err(PrioLevelError::DuplicateName(name));
+            }
+            names.push(name);
         }
     }
+    Ok(())
+}
+
+impl Prio {
+    pub fn level<'a>(&self, levels: &'a [PrioLevel]) -> Option<&'a PrioLevel> {
+        let rank = self.0?;
+        levels.iter().find(|level| level.rank == rank)
+    }
+
+    pub fn name(&self, levels: &[PrioLevel]) -> String {
+        match (self.level(levels), self.0) {
+            (Some(level), _) => level.name.clone(),
+            (None, Some(rank)) => rank.to_string(),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn random(levels: &[PrioLevel]) -> Option<Prio> {
+        Prio::parse_with(levels, "rnd").ok()
+    }
+
+    pub fn parse_with(levels: &[PrioLevel], value: &str) -> Result<Prio, PrioParseError> {
+        if value.eq_ignore_ascii_case("empty") {
+            return Ok(Prio::Empty);
+        }
+        levels
+            .iter()
+            .find(|level| level.matches(value))
+            .map(|level| Prio(Some(level.rank)))
+            .ok_or_else(|| PrioParseError {
+                value: value.to_string(),
+                expected: levels
+                    .iter()
+                    .map(|level| level.name.as_str())
+                    .chain(["empty"])
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            })
+    }
+}
+
+impl Ord for Prio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0, other.0) {
+            (Some(rank), Some(other)) => rank.cmp(&other),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for Prio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn custom() -> Vec<PrioLevel> {
+        vec![
+            PrioLevel::new("critical", 0, &["P0"], "red"),
+            PrioLevel::new("someday", 10, &[], ""),
+        ]
+    }
+
+    #[test]
+    fn should_parse_names_and_aliases_case_insensitively() {
+        let defaults = PrioLevel::defaults();
+        assert_eq!(Prio::parse_with(&defaults, "p1"), Ok(Prio::P1));
+        assert_eq!(Prio::parse_with(&defaults, "HIGH"), Ok(Prio::P1));
+        assert_eq!(Prio::parse_with(&defaults, "rnd"), Ok(Prio(Some(99))));
+        assert_eq!(Prio::parse_with(&defaults, "empty"), Ok(Prio::Empty));
+        assert_eq!(Prio::parse_with(&custom(), "p0"), Ok(Prio(Some(0))));
+        assert!(Prio::parse_with(&custom(), "p1").is_err());
+    }
+
+    #[test]
+    fn should_sort_by_rank_and_empty_last() {
+        let mut prios = vec![
+            Prio::Empty,
+            Prio(Some(99)),
+            Prio(Some(0)),
+            Prio::P2,
+            Prio::P1,
+        ];
+        prios.sort();
+        assert_eq!(
+            prios,
+            vec![
+                Prio(Some(0)),
+                Prio::P1,
+                Prio::P2,
+                Prio(Some(99)),
+                Prio::Empty
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_duplicate_levels() {
+        assert_eq!(validate_prio_levels(&PrioLevel::defaults()), Ok(()));
+        let mut levels = custom();
+        levels.push(PrioLevel::new("urgent", 5, &["critical"], ""));
+        assert_eq!(
+            validate_prio_levels(&levels),
+            Err(PrioLevelError::DuplicateName("critical".to_string()))
+        );
+        let mut levels = custom();
+        levels.push(PrioLevel::new("urgent", 10, &[], ""));
+        assert_eq!(
+            validate_prio_levels(&levels),
+            Err(PrioLevelError::DuplicateRank(10))
+        );
+    }
+
+    #[test]
+    fn should_name_prios_by_the_given_levels() {
+        assert_eq!(Prio::P1.name(&PrioLevel::defaults()), "P1");
+        assert_eq!(Prio(Some(0)).name(&custom()), "critical");
+        assert_eq!(Prio::P1.name(&custom()), "1");
+        assert_eq!(Prio::Empty.name(&custom()), "");
+    }
+
+    #[test]
+    fn should_find_random_level_by_name() {
+        assert_eq!(Prio::random(&PrioLevel::defaults()), Some(Prio(Some(99))));
+        assert_eq!(Prio::random(&custom()), None);
+        let levels = vec![PrioLevel::new("whenever", 7, &["rnd"], "")];
+        assert_eq!(Prio::random(&levels), Some(Prio(Some(7))));
+    }
 }
@@ -1,6 +1,8 @@
 use anyhow::Result;
 
-use crate::domain::{Datetime, ListFilters, Metadata, Prio, Status, Tag, TodoItem};
+use crate::domain::{
+    Datetime, ListDetails, ListFilters, Metadata, Prio, Selection, Status, Tag, TodoItem,
+};
 
 // --------- TodoListRepository --------- //
 
@@ -57,6 +59,17 @@ pub trait TodoItemResolve {
 
 pub trait TodoItemMetadata {
     fn fetch_item_and_metadata(&self, id: &str) -> Result<(TodoItem, Metadata)>;
+    fn fetch_list_with_metadata(&self, filters: ListFilters) -> Result<Vec<(TodoItem, Metadata)>>;
+}
+
+pub trait TodoItemExternalRef {
+    fn fetch_id_by_external_ref(&self, external_ref: &str) -> Result<Option<String>>;
+    fn set_external_ref(&self, id: &str, external_ref: &str) -> Result<()>;
+}
+
+pub trait TodoItemTransfer {
+    // all items or none, the copies get the next numbers of `target`
+    fn transfer(&self, target: &str, ids: &[(String, String)], remove: bool) -> Result<()>;
 }
 
 pub trait TodoItemRepository:
@@ -69,6 +82,8 @@ pub trait TodoItemRepository:
     + TodoItemQueryColumns
     + TodoItemResolve
     + TodoItemMetadata
+    + TodoItemExternalRef
+    + TodoItemTransfer
 {
 }
 
@@ -82,6 +97,8 @@ impl<T> TodoItemRepository for T where
         + TodoItemDelete
         + TodoItemResolve
         + TodoItemMetadata
+        + TodoItemExternalRef
+        + TodoItemTransfer
 {
 }
 
@@ -99,6 +116,19 @@ pub trait TodoListCreate {
 pub trait TodoListRead {
     fn fetch_all(&self) -> Result<Vec<String>>;
     fn fetch_id(&self, list_name: &str) -> Result<i64>;
+    fn fetch_details(&self) -> Result<Vec<ListDetails>>;
+}
+
+pub trait TodoListUpdate {
+    fn rename(&self, list_name: &str, new_name: &str) -> Result<()>;
+    // an empty description or color clears it
+    fn update_details(
+        &self,
+        list_name: &str,
+        description: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<()>;
+    fn set_archived(&self, list_name: &str, archived: bool) -> Result<()>;
 }
 
 pub trait TodoListDelete {
@@ -106,11 +136,24 @@ pub trait TodoListDelete {
 }
 
 pub trait TodoListRepository:
-    TodoListSchema + TodoListCreate + TodoListRead + TodoListDelete
+    TodoListSchema + TodoListCreate + TodoListRead + TodoListUpdate + TodoListDelete
 {
 }
 
 impl<T> TodoListRepository for T where
-    T: TodoListSchema + TodoListCreate + TodoListRead + TodoListDelete
+    T: TodoListSchema + TodoListCreate + TodoListRead + TodoListUpdate + TodoListDelete
 {
 }
+
+// --------- SelectionRepository --------- //
+
+pub trait SelectionRepository {
+    fn fetch_selection(&self) -> Result<Selection>;
+    fn save_selection(&self, selection: &Selection) -> Result<()>;
+}
+
+// --------- Transactional --------- //
+
+pub trait Transactional {
+    fn transaction<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T>;
+}
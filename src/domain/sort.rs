@@ -0,0 +1,240 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::domain::{Datetime, Metadata, Prio, Status, TodoItem};
+use crate::util::parse_task;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Id,
+    Prio,
+    Tag,
+    Due,
+    Created,
+    Updated,
+    Title,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortSpec(pub Vec<SortKey>);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SortParseError {
+    #[error("unknown sort key '{0}', expected one of: id, prio, tag, due, created, updated, title, status")]
+    UnknownKey(String),
+    #[error("empty sort key")]
+    Empty,
+}
+
+impl FromStr for SortField {
+    type Err = SortParseError;
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        match field {
+            "id" => Ok(SortField::Id),
+            "prio" => Ok(SortField::Prio),
+            "tag" => Ok(SortField::Tag),
+            "due" => Ok(SortField::Due),
+            "created" => Ok(SortField::Created),
+            "updated" => Ok(SortField::Updated),
+            "title" => Ok(SortField::Title),
+            "status" => Ok(SortField::Status),
+            "" => Err(SortParseError::Empty),
+            _ => Err(SortParseError::UnknownKey(field.to_string())),
+        }
+    }
+}
+
+impl FromStr for SortSpec {
+    type Err = SortParseError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        spec.split(',')
+            .map(|key| {
+                let key = key.trim();
+                let (field, descending) = match key.strip_prefix('-') {
+                    Some(field) => (field, true),
+                    None => (key, false),
+                };
+                Ok(SortKey {
+                    field: SortField::from_str(field)?,
+                    descending,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(SortSpec)
+    }
+}
+
+impl SortSpec {
+    // missing values (no tag, prio or due date) go last in either direction
+    pub fn sort(&self, entries: &mut [(TodoItem, Metadata)]) {
+        entries.sort_by(|a, b| {
+            self.0
+                .iter()
+                .map(|key| key.compare(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+impl SortKey {
+    fn compare(&self, a: &(TodoItem, Metadata), b: &(TodoItem, Metadata)) -> Ordering {
+        let ((a, a_meta), (b, b_meta)) = (a, b);
+        let (missing, ordering) = match self.field {
+            // newest first, which `sort_by = "id"` has always meant
+            SortField::Id => (Ordering::Equal, b.id.cmp(&a.id)),
+            SortField::Prio => (
+                (a.prio == Prio::Empty).cmp(&(b.prio == Prio::Empty)),
+                a.prio.cmp(&b.prio),
+            ),
+            SortField::Tag => (
+                a.tag.0.is_empty().cmp(&b.tag.0.is_empty()),
+                a.tag.cmp(&b.tag),
+            ),
+            SortField::Due => (
+                (a.due == Datetime::epoch()).cmp(&(b.due == Datetime::epoch())),
+                a.due.cmp(&b.due),
+            ),
+            SortField::Created => (Ordering::Equal, a_meta.created_at.cmp(&b_meta.created_at)),
+            SortField::Updated => (
+                Ordering::Equal,
+                a_meta.last_updated.cmp(&b_meta.last_updated),
+            ),
+            SortField::Title => (
+                Ordering::Equal,
+                parse_task(&a.task)
+                    .0
+                    .to_lowercase()
+                    .cmp(&parse_task(&b.task).0.to_lowercase()),
+            ),
+            SortField::Status => (
+                Ordering::Equal,
+                status_rank(a.status).cmp(&status_rank(b.status)),
+            ),
+        };
+        let ordering = if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        missing.then(ordering)
+    }
+}
+
+fn status_rank(status: Status) -> u8 {
+    match status {
+        Status::InProgress => 0,
+        Status::Open => 1,
+        Status::Blocked => 2,
+        Status::Closed => 3,
+        Status::Cancelled => 4,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::todo::test_support::entry;
+
+    fn at(timestamp: i64) -> Datetime {
+        Datetime { timestamp }
+    }
+
+    fn ids(entries: &[(TodoItem, Metadata)]) -> Vec<&str> {
+        entries.iter().map(|(item, _)| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn should_parse_keys_with_direction() -> anyhow::Result<()> {
+        let spec = SortSpec::from_str("prio, -created")?;
+        assert_eq!(
+            spec.0,
+            vec![
+                SortKey {
+                    field: SortField::Prio,
+                    descending: false
+                },
+                SortKey {
+                    field: SortField::Created,
+                    descending: true
+                }
+            ]
+        );
+        assert_eq!(
+            SortSpec::from_str("prio,nope"),
+            Err(SortParseError::UnknownKey("nope".to_string()))
+        );
+        assert_eq!(SortSpec::from_str("prio,"), Err(SortParseError::Empty));
+        Ok(())
+    }
+
+    #[test]
+    fn should_sort_by_multiple_keys() -> anyhow::Result<()> {
+        let mut entries = vec![
+            entry("a").prio(Prio::P2).created(at(1)).build(),
+            entry("b")
+                .prio(Prio::P1)
+                .due(at(200))
+                .created(at(2))
+                .build(),
+            entry("c")
+                .prio(Prio::P1)
+                .due(at(100))
+                .created(at(3))
+                .build(),
+            entry("d").prio(Prio::P1).created(at(4)).build(),
+            entry("e").prio(Prio::P2).created(at(5)).build(),
+        ];
+        SortSpec::from_str("prio,due,-created")?.sort(&mut entries);
+        assert_eq!(ids(&entries), vec!["c", "b", "d", "e", "a"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_missing_due_dates_last_when_descending() -> anyhow::Result<()> {
+        let mut entries = vec![
+            entry("a").created(at(1)).build(),
+            entry("b").due(at(100)).created(at(2)).build(),
+            entry("c").due(at(200)).created(at(3)).build(),
+        ];
+        SortSpec::from_str("-due")?.sort(&mut entries);
+        assert_eq!(ids(&entries), vec!["c", "b", "a"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_break_ties_stably() -> anyhow::Result<()> {
+        let mut entries = vec![
+            entry("b").prio(Prio::P1).created(at(1)).build(),
+            entry("a").prio(Prio::P1).created(at(1)).build(),
+            entry("c").prio(Prio::P1).created(at(1)).build(),
+        ];
+        SortSpec::from_str("prio")?.sort(&mut entries);
+        assert_eq!(ids(&entries), vec!["b", "a", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_newest_ids_first() -> anyhow::Result<()> {
+        let mut entries = vec![
+            entry("01a").build(),
+            entry("01c").build(),
+            entry("01b").build(),
+        ];
+        SortSpec::from_str("id")?.sort(&mut entries);
+        assert_eq!(ids(&entries), vec!["01c", "01b", "01a"]);
+        SortSpec::from_str("-id")?.sort(&mut entries);
+        assert_eq!(ids(&entries), vec!["01a", "01b", "01c"]);
+        Ok(())
+    }
+}
@@ -2,6 +2,26 @@ use clap::ValueEnum;
 
 #[derive(Debug, PartialEq, PartialOrd, ValueEnum, Clone, Copy)]
 pub enum Status {
+    #[value(alias = "done")]
     Closed,
     Open,
+    InProgress,
+    Blocked,
+    Cancelled,
+}
+
+impl Status {
+    pub fn is_active(&self) -> bool {
+        matches!(self, Status::Open | Status::InProgress | Status::Blocked)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Open => "open",
+            Status::InProgress => "in-progress",
+            Status::Blocked => "blocked",
+            Status::Closed => "closed",
+            Status::Cancelled => "cancelled",
+        }
+    }
 }
@@ -0,0 +1,330 @@
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::str::FromStr;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Alignment, Modify},
+    Table,
+};
+use thiserror::Error;
+
+use crate::application::config::{paint, Config};
+use crate::domain::table::apply_table_style;
+use crate::domain::{Datetime, FormatContext, ListDetails, Metadata, Status, TodoItem};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListSummary {
+    pub details: ListDetails,
+    pub current: bool,
+    // open, in progress and blocked tasks
+    pub open: usize,
+    pub closed: usize,
+    pub overdue: usize,
+    pub due_today: usize,
+    pub last_activity: Option<Datetime>,
+}
+
+impl ListSummary {
+    pub fn new(
+        details: ListDetails,
+        entries: &[(TodoItem, Metadata)],
+        ctx: &FormatContext,
+        current: bool,
+    ) -> Self {
+        let active = || entries.iter().filter(|(item, _)| item.status.is_active());
+        Self {
+            details,
+            current,
+            open: active().count(),
+            closed: entries
+                .iter()
+                .filter(|(item, _)| item.status == Status::Closed)
+                .count(),
+            overdue: active()
+                .filter(|(item, _)| ctx.is_overdue(&item.due))
+                .count(),
+            due_today: active()
+                .filter(|(item, _)| {
+                    item.due != Datetime::epoch() && ctx.days_from_today(&item.due) == 0
+                })
+                .count(),
+            last_activity: entries
+                .iter()
+                .map(|(_, metadata)| metadata.last_updated.max(metadata.created_at))
+                .max(),
+        }
+    }
+}
+
+pub fn summary_table(summaries: &[ListSummary], ctx: &FormatContext, config: &Config) -> Table {
+    let mut builder = Builder::default();
+    builder.push_record([
+        "",
+        "list",
+        "open",
+        "closed",
+        "overdue",
+        "today",
+        "activity",
+        "description",
+    ]);
+    for summary in summaries {
+        let details = &summary.details;
+        let overdue = match summary.overdue {
+            0 => String::new(),
+            count => paint(&count.to_string(), &config.theme.overdue),
+        };
+        let count = |count: usize| match count {
+            0 => String::new(),
+            count => count.to_string(),
+        };
+        builder.push_record([
+            if summary.current { "*" } else { "" }.to_string(),
+            paint(&details.name, details.color.as_deref().unwrap_or_default()),
+            count(summary.open),
+            count(summary.closed),
+            overdue,
+            count(summary.due_today),
+            summary
+                .last_activity
+                .map(|datetime| ctx.date(&datetime))
+                .unwrap_or_default(),
+            details.description.clone().unwrap_or_default(),
+        ]);
+    }
+    let mut table = builder.build();
+    apply_table_style(&mut table, config);
+    table.with(Modify::new(Columns::new(2..6)).with(Alignment::right()));
+    table
+}
+
+// Field names are part of the machine-readable output and must stay stable.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ListSummaryView {
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub archived: bool,
+    pub current: bool,
+    pub open: usize,
+    pub closed: usize,
+    pub overdue: usize,
+    pub due_today: usize,
+    pub last_activity: Option<String>,
+}
+
+impl From<&ListSummary> for ListSummaryView {
+    fn from(summary: &ListSummary) -> Self {
+        let details = summary.details.clone();
+        Self {
+            name: details.name,
+            description: details.description,
+            color: details.color,
+            archived: details.archived,
+            current: summary.current,
+            open: summary.open,
+            closed: summary.closed,
+            overdue: summary.overdue,
+            due_today: summary.due_today,
+            last_activity: summary.last_activity.map(|datetime| datetime.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryField {
+    Name,
+    Open,
+    Closed,
+    Overdue,
+    Today,
+    Activity,
+}
+
+// keys like `-overdue,name`, where `-` sorts descending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummarySort(pub Vec<(SummaryField, bool)>);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SummarySortParseError {
+    #[error(
+        "unknown sort key '{0}', expected one of: name, open, closed, overdue, today, activity"
+    )]
+    UnknownKey(String),
+    #[error("empty sort key")]
+    Empty,
+}
+
+impl FromStr for SummaryField {
+    type Err = SummarySortParseError;
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        match field {
+            "name" => Ok(SummaryField::Name),
+            "open" => Ok(SummaryField::Open),
+            "closed" => Ok(SummaryField::Closed),
+            "overdue" => Ok(SummaryField::Overdue),
+            "today" => Ok(SummaryField::Today),
+            "activity" => Ok(SummaryField::Activity),
+            "" => Err(SummarySortParseError::Empty),
+            _ => Err(SummarySortParseError::UnknownKey(field.to_string())),
+        }
+    }
+}
+
+impl FromStr for SummarySort {
+    type Err = SummarySortParseError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        spec.split(',')
+            .map(|key| {
+                let key = key.trim();
+                match key.strip_prefix('-') {
+                    Some(field) => Ok((SummaryField::from_str(field)?, true)),
+                    None => Ok((SummaryField::from_str(key)?, false)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(SummarySort)
+    }
+}
+
+impl SummarySort {
+    // lists without any activity go last in either direction
+    pub fn sort(&self, summaries: &mut [ListSummary]) {
+        summaries.sort_by(|a, b| {
+            self.0
+                .iter()
+                .map(|(field, descending)| {
+                    let ordering = match field {
+                        SummaryField::Name => a.details.name.cmp(&b.details.name),
+                        SummaryField::Open => a.open.cmp(&b.open),
+                        SummaryField::Closed => a.closed.cmp(&b.closed),
+                        SummaryField::Overdue => a.overdue.cmp(&b.overdue),
+                        SummaryField::Today => a.due_today.cmp(&b.due_today),
+                        SummaryField::Activity => match (a.last_activity, b.last_activity) {
+                            (Some(a), Some(b)) => a.cmp(&b),
+                            (a, b) => return b.is_some().cmp(&a.is_some()),
+                        },
+                    };
+                    if *descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Duration, Local, TimeZone};
+
+    use crate::domain::todo::test_support::entry;
+
+    fn ctx() -> FormatContext {
+        // a Wednesday
+        let now = Local.with_ymd_and_hms(2025, 6, 11, 12, 0, 0).unwrap();
+        FormatContext::new("%x", 7, now)
+    }
+
+    fn in_days(days: i64) -> Datetime {
+        Datetime {
+            timestamp: ctx().now.timestamp + Duration::days(days).num_seconds(),
+        }
+    }
+
+    fn summary(name: &str, overdue: usize, last_activity: Option<i64>) -> ListSummary {
+        ListSummary {
+            details: ListDetails {
+                name: name.to_string(),
+                ..ListDetails::default()
+            },
+            current: false,
+            open: 0,
+            closed: 0,
+            overdue,
+            due_today: 0,
+            last_activity: last_activity.map(|timestamp| Datetime { timestamp }),
+        }
+    }
+
+    fn names(summaries: &[ListSummary]) -> Vec<&str> {
+        summaries
+            .iter()
+            .map(|summary| summary.details.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_count_tasks() {
+        let entries = vec![
+            entry("open")
+                .status(Status::Open)
+                .due(in_days(-2))
+                .updated(Datetime { timestamp: 10 })
+                .build(),
+            entry("inprogress")
+                .status(Status::InProgress)
+                .due(in_days(0))
+                .updated(Datetime { timestamp: 30 })
+                .build(),
+            entry("blocked")
+                .status(Status::Blocked)
+                .updated(Datetime { timestamp: 20 })
+                .build(),
+            entry("closed")
+                .status(Status::Closed)
+                .due(in_days(-2))
+                .updated(Datetime { timestamp: 5 })
+                .build(),
+            entry("cancelled")
+                .status(Status::Cancelled)
+                .due(in_days(0))
+                .updated(Datetime { timestamp: 5 })
+                .build(),
+        ];
+        let summary = ListSummary::new(ListDetails::default(), &entries, &ctx(), true);
+        assert_eq!(summary.open, 3);
+        assert_eq!(summary.closed, 1);
+        assert_eq!(summary.overdue, 1);
+        assert_eq!(summary.due_today, 1);
+        assert_eq!(summary.last_activity, Some(Datetime { timestamp: 30 }));
+    }
+
+    #[test]
+    fn should_summarize_empty_list() {
+        let summary = ListSummary::new(ListDetails::default(), &[], &ctx(), false);
+        assert_eq!((summary.open, summary.closed), (0, 0));
+        assert_eq!(summary.last_activity, None);
+    }
+
+    #[test]
+    fn should_sort_by_keys() -> Result<(), SummarySortParseError> {
+        let mut summaries = vec![
+            summary("b", 1, Some(20)),
+            summary("c", 3, None),
+            summary("a", 1, Some(10)),
+        ];
+        SummarySort::from_str("-overdue,name")?.sort(&mut summaries);
+        assert_eq!(names(&summaries), vec!["c", "a", "b"]);
+        // lists without activity go last
+        SummarySort::from_str("-activity")?.sort(&mut summaries);
+        assert_eq!(names(&summaries), vec!["b", "a", "c"]);
+        SummarySort::from_str("activity")?.sort(&mut summaries);
+        assert_eq!(names(&summaries), vec!["a", "b", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_key() {
+        assert_eq!(
+            SummarySort::from_str("open,size"),
+            Err(SummarySortParseError::UnknownKey("size".to_string()))
+        );
+    }
+}
@@ -1,37 +1,55 @@
-use colored::Colorize;
 use tabled::{
     builder::Builder,
+    grid::util::string::string_width_multiline,
     settings::{
         format::{Format, FormatContent},
         object::{Columns, Object, Rows},
         style::Style,
-        Modify, Width,
+        Alignment, Modify, Width,
     },
     Table,
 };
 
-use crate::application::config::{Config, TableStyle};
-use crate::domain::{TodoItem, TodoItemRow};
+use crate::application::config::{paint, Config, TableStyle, Theme};
+use crate::domain::{Column, FormatContext, Metadata, PrioLevel, TodoItem};
+use crate::util::parse_task;
+
+const MIN_TITLE_WIDTH: usize = 10;
 
 pub struct TodoListTable {
     pub table: Table,
 }
 
 impl TodoListTable {
-    pub fn new(entries: &[TodoItem], config: &Config) -> Self {
-        let mut table = build_table(entries, config);
-        table
-            .with(Modify::new(Rows::new(1..).intersect(Columns::single(0))).with(format_id(config)))
-            .with(Modify::new(Rows::new(1..).intersect(Columns::single(0))).with(color_id()))
-            .with(Modify::new(Rows::new(1..).intersect(Columns::single(2))).with(color_status()))
-            .with(Modify::new(Rows::new(1..).intersect(Columns::single(3))).with(color_prio()))
-            .with(Modify::new(Columns::single(0)).with(Width::increase(5))) // id
-            .with(Modify::new(Columns::single(1)).with(Width::wrap(60))) // task
-            .with(Modify::new(Columns::single(2)).with(Width::increase(3))) // status
-            .with(Modify::new(Columns::single(3)).with(Width::increase(3))) // prio
-            .with(Modify::new(Columns::single(4)).with(Width::increase(3))) // due
-            .with(Modify::new(Columns::single(5)).with(Width::wrap(12))); // tag
+    pub fn new(
+        entries: &[(TodoItem, Metadata)],
+        ctx: &FormatContext,
+        config: &Config,
+        columns: &[Column],
+    ) -> Self {
+        let theme = &config.theme;
+        let mut table = build_table(entries, columns, ctx, config);
+        for (idx, column) in columns.iter().enumerate() {
+            let cells = || Rows::new(1..).intersect(Columns::single(idx));
+            match column {
+                Column::Id => table
+                    .with(Modify::new(cells()).with(color_with(&theme.id)))
+                    .with(Modify::new(Columns::single(idx)).with(Width::increase(5))),
+                Column::Number => table
+                    .with(Modify::new(cells()).with(color_with(&theme.id)))
+                    .with(Modify::new(Columns::single(idx)).with(Alignment::right())),
+                Column::Status => table.with(Modify::new(cells()).with(color_status(theme))),
+                Column::Prio => table.with(Modify::new(cells()).with(color_prio(&ctx.levels))),
+                Column::Tag => table
+                    .with(Modify::new(cells()).with(color_with(&theme.tag)))
+                    .with(Modify::new(Columns::single(idx)).with(Width::wrap(12))),
+                _ => &mut table,
+            };
+        }
         apply_table_style(&mut table, config);
+        if let Some(idx) = columns.iter().position(|column| *column == Column::Title) {
+            fit_title(&mut table, entries, idx, terminal_width());
+        }
         Self { table }
     }
     pub fn print(&self) {
@@ -39,38 +57,60 @@ impl TodoListTable {
     }
 }
 
-fn build_table(entries: &[TodoItem], config: &Config) -> Table {
-    let show_due = config.style.show_due;
-    let show_tag = config.style.show_tag;
+fn build_table(
+    entries: &[(TodoItem, Metadata)],
+    columns: &[Column],
+    ctx: &FormatContext,
+    config: &Config,
+) -> Table {
     let mut builder = Builder::default();
-    let mut headers = vec!["id", "title", "status", "prio"];
-    if show_due {
-        headers.push("due")
-    };
-    if show_tag {
-        headers.push("tag")
-    };
-    builder.push_record(headers);
-    let items: Vec<TodoItemRow> = entries.iter().map(TodoItemRow::from).collect();
-    for item in items {
-        let mut row = vec![
-            item.id,
-            item.title,
-            item.status.to_string(),
-            item.prio.to_string(),
-        ];
-        if show_due {
-            row.push(item.due.to_string());
-        };
-        if show_tag {
-            row.push(item.tag.to_string());
-        };
-        builder.push_record(row);
+    builder.push_record(columns.iter().map(Column::header));
+    for (item, metadata) in entries {
+        builder.push_record(columns.iter().map(|column| match column {
+            Column::Due if item.status.is_active() && ctx.is_overdue(&item.due) => {
+                paint(&column.cell(item, metadata, ctx), &config.theme.overdue)
+            }
+            _ => column.cell(item, metadata, ctx),
+        }));
     }
     builder.build()
 }
 
-fn apply_table_style(table: &mut Table, config: &Config) {
+fn fit_title(
+    table: &mut Table,
+    entries: &[(TodoItem, Metadata)],
+    idx: usize,
+    max_width: Option<usize>,
+) {
+    let Some(max_width) = max_width else {
+        return;
+    };
+    let total_width = table.total_width();
+    if total_width <= max_width {
+        return;
+    }
+    let title_width = entries
+        .iter()
+        .map(|(item, _)| string_width_multiline(&parse_task(&item.task).0))
+        .max()
+        .unwrap_or_default();
+    let width = title_width
+        .saturating_sub(total_width - max_width)
+        .max(MIN_TITLE_WIDTH);
+    table.with(
+        Modify::new(Rows::new(1..).intersect(Columns::single(idx)))
+            .with(Width::truncate(width).suffix("…")),
+    );
+}
+
+pub(crate) fn terminal_width() -> Option<usize> {
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return Some(width as usize);
+    }
+    std::env::var("COLUMNS").ok()?.parse().ok()
+}
+
+pub(crate) fn apply_table_style(table: &mut Table, config: &Config) {
     let style = config.style.table.clone();
     match style.into() {
         TableStyle::Ascii => table.with(Style::ascii()),
@@ -81,33 +121,25 @@ fn apply_table_style(table: &mut Table, config: &Config) {
     };
 }
 
-fn color_id() -> FormatContent<impl FnMut(&str) -> String + Clone> {
-    Format::content(|cell: &str| cell.yellow().to_string())
-}
-
-fn color_prio() -> FormatContent<impl FnMut(&str) -> String + Clone> {
-    Format::content(|cell: &str| match cell {
-        "P1" => cell.red().to_string(),
-        "P2" => cell.yellow().to_string(),
-        "P3" => cell.green().to_string(),
-        "RND" => cell.purple().to_string(),
-        _ => cell.to_string(),
-    })
+fn color_with(color: &str) -> FormatContent<impl FnMut(&str) -> String + Clone + use<'_>> {
+    Format::content(move |cell: &str| paint(cell, color))
 }
 
-fn color_status() -> FormatContent<impl FnMut(&str) -> String + Clone> {
-    Format::content(|cell: &str| {
-        if cell.contains('✔') {
-            cell.green().to_string()
-        } else {
-            cell.to_string()
-        }
-    })
+fn color_prio(levels: &[PrioLevel]) -> FormatContent<impl FnMut(&str) -> String + Clone + use<'_>> {
+    Format::content(
+        |cell: &str| match levels.iter().find(|level| level.name == cell) {
+            Some(level) => paint(cell, &level.color),
+            None => cell.to_string(),
+        },
+    )
 }
 
-fn format_id(config: &Config) -> FormatContent<impl FnMut(&str) -> String + Clone + use<'_>> {
-    Format::content(|cell: &str| {
-        let id_length = config.style.id_length;
-        cell.chars().take(id_length).collect()
+fn color_status(theme: &Theme) -> FormatContent<impl FnMut(&str) -> String + Clone + use<'_>> {
+    Format::content(|cell: &str| match cell {
+        "▶" => paint(cell, &theme.in_progress),
+        "⊘" => paint(cell, &theme.blocked),
+        "✔" => paint(cell, &theme.done),
+        "–" => paint(cell, &theme.cancelled),
+        _ => cell.to_string(),
     })
 }
@@ -0,0 +1,345 @@
+use chrono::format::{Item, StrftimeItems};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::domain::{Datetime, FormatContext, Metadata, TodoItem};
+use crate::util::parse_task;
+
+// e.g. `{id:8} {prio} {title:.40} {due:%d.%m}`, see the README for the modifiers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(Field, Spec),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Id,
+    Number,
+    Title,
+    Body,
+    Task,
+    Status,
+    Prio,
+    Due,
+    Tag,
+    Created,
+    Updated,
+    List,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Spec {
+    align: Align,
+    width: Option<usize>,
+    max: Option<usize>,
+    date_format: Option<String>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TemplateParseError {
+    #[error("unclosed '{{' at column {0}")]
+    Unclosed(usize),
+    #[error("unmatched '}}' at column {0}, use '}}}}' for a literal brace")]
+    Unmatched(usize),
+    #[error("unknown field '{field}' at column {column}")]
+    UnknownField { field: String, column: usize },
+    #[error("invalid modifier '{spec}' for field '{field}' at column {column}")]
+    InvalidModifier {
+        field: String,
+        spec: String,
+        column: usize,
+    },
+}
+
+impl FromStr for Field {
+    type Err = ();
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        match field {
+            "id" => Ok(Field::Id),
+            "number" => Ok(Field::Number),
+            "title" => Ok(Field::Title),
+            "body" => Ok(Field::Body),
+            "task" => Ok(Field::Task),
+            "status" => Ok(Field::Status),
+            "prio" => Ok(Field::Prio),
+            "due" => Ok(Field::Due),
+            "tag" => Ok(Field::Tag),
+            "created" => Ok(Field::Created),
+            "updated" => Ok(Field::Updated),
+            "list" => Ok(Field::List),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Field {
+    fn is_date(&self) -> bool {
+        matches!(self, Field::Due | Field::Created | Field::Updated)
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateParseError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = template.chars().collect();
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut idx = 0;
+        while idx < chars.len() {
+            match chars[idx] {
+                '{' if chars.get(idx + 1) == Some(&'{') => {
+                    literal.push('{');
+                    idx += 2;
+                }
+                '}' if chars.get(idx + 1) == Some(&'}') => {
+                    literal.push('}');
+                    idx += 2;
+                }
+                '}' => return Err(TemplateParseError::Unmatched(idx + 1)),
+                '{' => {
+                    let start = idx;
+                    let end = chars[start..]
+                        .iter()
+                        .position(|c| *c == '}')
+                        .map(|offset| start + offset)
+                        .ok_or(TemplateParseError::Unclosed(start + 1))?;
+                    let placeholder: String = chars[start + 1..end].iter().collect();
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(parse_placeholder(&placeholder, start + 2)?);
+                    idx = end + 1;
+                }
+                c => {
+                    literal.push(c);
+                    idx += 1;
+                }
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Template { segments })
+    }
+}
+
+fn parse_placeholder(placeholder: &str, column: usize) -> Result<Segment, TemplateParseError> {
+    let (name, spec) = match placeholder.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec)),
+        None => (placeholder.trim(), None),
+    };
+    let field = Field::from_str(name).map_err(|_| TemplateParseError::UnknownField {
+        field: name.to_string(),
+        column,
+    })?;
+    let invalid = || TemplateParseError::InvalidModifier {
+        field: name.to_string(),
+        spec: spec.unwrap_or_default().to_string(),
+        column,
+    };
+    let spec = match spec {
+        None => Spec::default(),
+        Some(fmt) if fmt.starts_with('%') => {
+            let valid = !StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error));
+            if !field.is_date() || !valid {
+                return Err(invalid());
+            }
+            Spec {
+                date_format: Some(fmt.to_string()),
+                ..Spec::default()
+            }
+        }
+        Some(spec) => parse_spec(spec).ok_or_else(invalid)?,
+    };
+    Ok(Segment::Field(field, spec))
+}
+
+fn parse_spec(spec: &str) -> Option<Spec> {
+    let (align, rest) = match spec.chars().next() {
+        Some('<') => (Align::Left, &spec[1..]),
+        Some('>') => (Align::Right, &spec[1..]),
+        Some('^') => (Align::Center, &spec[1..]),
+        _ => (Align::Left, spec),
+    };
+    let (width, max) = match rest.split_once('.') {
+        Some((width, max)) => (width, Some(max.parse::<usize>().ok()?)),
+        None => (rest, None),
+    };
+    let width = if width.is_empty() {
+        None
+    } else {
+        Some(width.parse::<usize>().ok()?)
+    };
+    Some(Spec {
+        align,
+        width,
+        max,
+        date_format: None,
+    })
+}
+
+impl Template {
+    pub fn render(&self, item: &TodoItem, metadata: &Metadata, ctx: &FormatContext) -> String {
+        let (title, body) = parse_task(&item.task);
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Field(field, spec) => {
+                    let value = match field {
+                        Field::Id => item.id.clone(),
+                        Field::Number => metadata.number.to_string(),
+                        Field::Title => title.clone(),
+                        Field::Body => body.replace('\n', " "),
+                        Field::Task => item.task.replace('\n', " "),
+                        Field::Status => item.status.name().to_string(),
+                        Field::Prio => ctx.prio(item.prio),
+                        Field::Due => format_date(&item.due, spec, ctx),
+                        Field::Tag => item.tag.to_string(),
+                        Field::Created => format_date(&metadata.created_at, spec, ctx),
+                        Field::Updated => format_date(&metadata.last_updated, spec, ctx),
+                        Field::List => metadata.list.clone(),
+                    };
+                    apply_spec(value, spec)
+                }
+            })
+            .collect()
+    }
+}
+
+fn format_date(date: &Datetime, spec: &Spec, ctx: &FormatContext) -> String {
+    match &spec.date_format {
+        _ if *date == Datetime::epoch() => String::new(),
+        Some(fmt) => date.to_local().format(fmt).to_string(),
+        None => ctx.date(date),
+    }
+}
+
+fn apply_spec(value: String, spec: &Spec) -> String {
+    let mut value = value;
+    if let Some(max) = spec.max {
+        if value.chars().count() > max {
+            value = match max {
+                0 => String::new(),
+                _ => value.chars().take(max - 1).chain(['…']).collect(),
+            };
+        }
+    }
+    if let Some(width) = spec.width {
+        let len = value.chars().count();
+        if len > width {
+            value = value.chars().take(width).collect();
+        } else {
+            let pad = width - len;
+            value = match spec.align {
+                Align::Left => format!("{value}{}", " ".repeat(pad)),
+                Align::Right => format!("{}{value}", " ".repeat(pad)),
+                Align::Center => format!(
+                    "{}{value}{}",
+                    " ".repeat(pad / 2),
+                    " ".repeat(pad - pad / 2)
+                ),
+            };
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::todo::test_support::entry;
+    use crate::domain::Prio;
+
+    fn ctx() -> FormatContext {
+        FormatContext::new("%x", 7, chrono::Local::now())
+    }
+
+    fn sample() -> anyhow::Result<(TodoItem, Metadata)> {
+        Ok(entry("0123456789abcdef")
+            .task("A rather long title\n\nBody")
+            .prio(Prio::P1)
+            .due(Datetime::from_str("24/12/2025")?)
+            .tag("work")
+            .created(Datetime::from_str("01/12/2025")?)
+            .updated(Datetime::from_str("02/12/2025")?)
+            .build())
+    }
+
+    #[test]
+    fn should_render_fields_and_modifiers() -> anyhow::Result<()> {
+        let (item, metadata) = sample()?;
+        let template = Template::from_str("{id:8} {prio} {title:.8} {due:%d.%m} {tag}")?;
+        assert_eq!(
+            template.render(&item, &metadata, &ctx()),
+            "01234567 P1 A rathe… 24.12 #work"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_pad_and_align() -> anyhow::Result<()> {
+        let (item, metadata) = sample()?;
+        let template = Template::from_str("[{prio:>4}|{prio:<4}|{prio:^4}]")?;
+        assert_eq!(
+            template.render(&item, &metadata, &ctx()),
+            "[  P1|P1  | P1 ]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_escaped_braces_and_metadata() -> anyhow::Result<()> {
+        let (item, metadata) = sample()?;
+        let template = Template::from_str("{{{created:%Y-%m-%d}}}")?;
+        assert_eq!(template.render(&item, &metadata, &ctx()), "{2025-12-01}");
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_error_positions() {
+        assert_eq!(
+            Template::from_str("{id} {nope}"),
+            Err(TemplateParseError::UnknownField {
+                field: "nope".to_string(),
+                column: 7
+            })
+        );
+        assert_eq!(
+            Template::from_str("{id} {title"),
+            Err(TemplateParseError::Unclosed(6))
+        );
+        assert_eq!(
+            Template::from_str("id}"),
+            Err(TemplateParseError::Unmatched(3))
+        );
+    }
+
+    #[test]
+    fn should_reject_date_format_on_non_date_field() {
+        assert!(matches!(
+            Template::from_str("{title:%d}"),
+            Err(TemplateParseError::InvalidModifier { .. })
+        ));
+        assert!(matches!(
+            Template::from_str("{title:x}"),
+            Err(TemplateParseError::InvalidModifier { .. })
+        ));
+    }
+}
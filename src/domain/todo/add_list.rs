@@ -1,14 +1,194 @@
 use anyhow::{bail, Context, Result};
 
-use crate::domain::{TodoList, TodoListCreate};
+use crate::domain::{TodoList, TodoListCreate, TodoListRead};
+
+// List names end up unquoted in SQL as the names of their tables.
+const SQL_KEYWORDS: [&str; 147] = [
+    "abort",
+    "action",
+    "add",
+    "after",
+    "all",
+    "alter",
+    "always",
+    "analyze",
+    "and",
+    "as",
+    "asc",
+    "attach",
+    "autoincrement",
+    "before",
+    "begin",
+    "between",
+    "by",
+    "cascade",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "column",
+    "commit",
+    "conflict",
+    "constraint",
+    "create",
+    "cross",
+    "current",
+    "current_date",
+    "current_time",
+    "current_timestamp",
+    "database",
+    "default",
+    "deferrable",
+    "deferred",
+    "delete",
+    "desc",
+    "detach",
+    "distinct",
+    "do",
+    "drop",
+    "each",
+    "else",
+    "end",
+    "escape",
+    "except",
+    "exclude",
+    "exclusive",
+    "exists",
+    "explain",
+    "fail",
+    "filter",
+    "first",
+    "following",
+    "for",
+    "foreign",
+    "from",
+    "full",
+    "generated",
+    "glob",
+    "group",
+    "groups",
+    "having",
+    "if",
+    "ignore",
+    "immediate",
+    "in",
+    "index",
+    "indexed",
+    "initially",
+    "inner",
+    "insert",
+    "instead",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "key",
+    "last",
+    "left",
+    "like",
+    "limit",
+    "match",
+    "materialized",
+    "natural",
+    "no",
+    "not",
+    "nothing",
+    "notnull",
+    "null",
+    "nulls",
+    "of",
+    "offset",
+    "on",
+    "or",
+    "order",
+    "others",
+    "outer",
+    "over",
+    "partition",
+    "plan",
+    "pragma",
+    "preceding",
+    "primary",
+    "query",
+    "raise",
+    "range",
+    "recursive",
+    "references",
+    "regexp",
+    "reindex",
+    "release",
+    "rename",
+    "replace",
+    "restrict",
+    "returning",
+    "right",
+    "rollback",
+    "row",
+    "rows",
+    "savepoint",
+    "select",
+    "set",
+    "table",
+    "temp",
+    "temporary",
+    "then",
+    "ties",
+    "to",
+    "transaction",
+    "trigger",
+    "unbounded",
+    "union",
+    "unique",
+    "update",
+    "using",
+    "vacuum",
+    "values",
+    "view",
+    "virtual",
+    "when",
+    "where",
+    "window",
+    "with",
+    "without",
+];
+const RESERVED_TABLES: [&str; 2] = ["collection", "state"];
 
 impl TodoList {
-    pub fn add_list(&self, repo: &impl TodoListCreate, list: &str) -> Result<()> {
-        if list.is_empty() {
-            bail!("✘ Can't add a list without name")
-        } else {
-            repo.add(list).context("✘ Couldn't add new list")
+    pub fn add_list<R>(&self, repo: &R, list: &str) -> Result<()>
+    where
+        R: TodoListCreate + TodoListRead,
+    {
+        self.check_list_name(repo, list)?;
+        repo.add(list).context("✘ Couldn't add new list")
+    }
+
+    pub fn check_list_name(&self, repo: &impl TodoListRead, list: &str) -> Result<()> {
+        let mut chars = list.chars();
+        let Some(first) = chars.next() else {
+            bail!("✘ A list needs a name")
+        };
+        if !(first.is_ascii_alphabetic() || first == '_')
+            || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            bail!("✘ Invalid list name '{list}', use letters, digits and underscores, not starting with a digit")
+        }
+        let lowercase = list.to_ascii_lowercase();
+        if SQL_KEYWORDS.contains(&lowercase.as_str())
+            || RESERVED_TABLES.contains(&lowercase.as_str())
+            || lowercase.starts_with("sqlite_")
+        {
+            bail!("✘ The name '{list}' is reserved, pick another name for the list")
         }
+        // table names are case-insensitive
+        let collection = self.get_collection(repo)?;
+        if let Some(name) = collection
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(list))
+        {
+            bail!("✘ There is a list named '{name}' already")
+        }
+        Ok(())
     }
 }
 
@@ -18,7 +198,7 @@ pub mod test {
     use anyhow::Result;
     use std::cell::RefCell;
 
-    use crate::domain::TodoListCreate;
+    use crate::domain::{ListDetails, TodoListCreate};
 
     struct FakeListRepo {
         todo_lists: RefCell<Vec<String>>,
@@ -43,6 +223,20 @@ pub mod test {
         }
     }
 
+    impl TodoListRead for FakeListRepo {
+        fn fetch_all(&self) -> Result<Vec<String>> {
+            Ok(self.todo_lists.borrow().clone())
+        }
+
+        fn fetch_id(&self, _: &str) -> Result<i64> {
+            unreachable!()
+        }
+
+        fn fetch_details(&self) -> Result<Vec<ListDetails>> {
+            unreachable!()
+        }
+    }
+
     #[test]
     fn should_err_on_empty_list_name() {
         let repo = FakeListRepo::new();
@@ -55,8 +249,35 @@ pub mod test {
         let repo = FakeListRepo::new();
         assert_eq!(repo.len(), 0);
         let todo_list = TodoList::new();
-        todo_list.add_list(&repo, "test-new-list")?;
+        todo_list.add_list(&repo, "test_new_list")?;
         assert_eq!(repo.len(), 1);
         Ok(())
     }
+
+    #[test]
+    fn should_err_on_names_unfit_for_a_table() {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        for list in ["my list", "my-list", "work:today", "x;drop", "1st", "été"] {
+            let err = todo_list.add_list(&repo, list).unwrap_err();
+            assert!(err.to_string().starts_with("✘ Invalid list name"), "{list}");
+        }
+        for list in ["select", "Order", "state", "sqlite_master"] {
+            let err = todo_list.add_list(&repo, list).unwrap_err();
+            assert!(err
+                .to_string()
+                .ends_with("is reserved, pick another name for the list"));
+        }
+        assert_eq!(repo.len(), 0);
+    }
+
+    #[test]
+    fn should_err_on_existing_list_name() -> Result<()> {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        todo_list.add_list(&repo, "work")?;
+        let err = todo_list.add_list(&repo, "Work").unwrap_err();
+        assert_eq!(err.to_string(), "✘ There is a list named 'work' already");
+        Ok(())
+    }
 }
@@ -1,7 +1,6 @@
-use tabled::Tabled;
+use serde::{Deserialize, Serialize};
 
-use crate::domain::{Datetime, Prio, Status, Tag};
-use crate::util::parse_task;
+use crate::domain::{Datetime, FilterExpr, Prio, Status, Tag};
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct TodoList {
@@ -19,6 +18,21 @@ impl Default for TodoList {
     }
 }
 
+// archived lists are left out of overviews, but can still be used by name
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ListDetails {
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub archived: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Selection {
+    pub current: Option<String>,
+    pub previous: Option<String>,
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct TodoItem {
     pub id: String,
@@ -29,41 +43,48 @@ pub struct TodoItem {
     pub tag: Tag,
 }
 
-#[derive(Tabled)]
-pub struct TodoItemRow {
-    pub id: String,
-    pub title: String,
-    pub status: Status,
-    pub prio: Prio,
-    pub due: Datetime,
-    pub tag: Tag,
-}
-
-impl From<&TodoItem> for TodoItemRow {
-    fn from(item: &TodoItem) -> Self {
-        let (title, _message) = parse_task(&item.task);
-        Self {
-            id: item.id.clone(),
-            title,
-            status: item.status,
-            prio: item.prio,
-            due: item.due,
-            tag: item.tag.clone(),
-        }
-    }
-}
-
 #[derive(Default, Clone, Debug)]
 pub struct ListFilters {
     pub status: Option<StatusFilter>,
     pub prio: Option<Prio>,
     pub due: Option<Datetime>,
     pub tag: Option<Tag>,
+    pub expr: Option<FilterExpr>,
 }
 
-#[derive(clap::ValueEnum, Clone, Debug)]
+impl ListFilters {
+    // `status` applies unless the expression tests the status itself
+    pub fn status_or(&self, status: StatusFilter) -> Option<StatusFilter> {
+        match &self.expr {
+            Some(expr) if expr.mentions_status() => self.status,
+            _ => Some(self.status.unwrap_or(status)),
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub enum StatusFilter {
     All,
+    /// Open, in progress and blocked
     Do,
     Done,
+    Open,
+    InProgress,
+    Blocked,
+    Cancelled,
+}
+
+impl StatusFilter {
+    pub fn matches(&self, status: Status) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Do => status.is_active(),
+            StatusFilter::Done => status == Status::Closed,
+            StatusFilter::Open => status == Status::Open,
+            StatusFilter::InProgress => status == Status::InProgress,
+            StatusFilter::Blocked => status == Status::Blocked,
+            StatusFilter::Cancelled => status == Status::Cancelled,
+        }
+    }
 }
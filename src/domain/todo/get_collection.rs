@@ -1,11 +1,25 @@
 use anyhow::{Context, Result};
 
-use crate::domain::{TodoList, TodoListRead};
+use crate::domain::{ListDetails, TodoList, TodoListRead};
 
 impl TodoList {
+    // archived lists included
     pub fn get_collection(&self, repo: &impl TodoListRead) -> Result<Vec<String>> {
         repo.fetch_all().context("✘ Couldn't fetch collection")
     }
+
+    pub fn get_collection_details(&self, repo: &impl TodoListRead) -> Result<Vec<ListDetails>> {
+        repo.fetch_details().context("✘ Couldn't fetch collection")
+    }
+
+    pub fn get_active_lists(&self, repo: &impl TodoListRead) -> Result<Vec<String>> {
+        let lists = self.get_collection_details(repo)?;
+        Ok(lists
+            .into_iter()
+            .filter(|list| !list.archived)
+            .map(|list| list.name)
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -37,6 +51,19 @@ pub mod test {
         fn fetch_id(&self, _: &str) -> Result<i64> {
             unreachable!()
         }
+
+        fn fetch_details(&self) -> Result<Vec<ListDetails>> {
+            Ok(self
+                .collection
+                .borrow()
+                .iter()
+                .map(|name| ListDetails {
+                    name: name.clone(),
+                    archived: name.ends_with('2'),
+                    ..ListDetails::default()
+                })
+                .collect())
+        }
     }
 
     struct FailingListRepo;
@@ -49,6 +76,10 @@ pub mod test {
         fn fetch_id(&self, _: &str) -> Result<i64> {
             unreachable!()
         }
+
+        fn fetch_details(&self) -> Result<Vec<ListDetails>> {
+            bail!("Fake error when fetching collection")
+        }
     }
 
     #[test]
@@ -60,6 +91,14 @@ pub mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_leave_out_archived_lists() -> Result<()> {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        assert_eq!(todo_list.get_active_lists(&repo)?, vec!["todo-list-1"]);
+        Ok(())
+    }
+
     #[test]
     fn should_provide_context_upon_failure() {
         let repo = FailingListRepo;
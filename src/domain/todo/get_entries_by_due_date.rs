@@ -88,8 +88,7 @@ pub mod test {
                     todo.due.timestamp == epoch_seconds
                         && match filters.status {
                             Some(StatusFilter::All) => true,
-                            Some(StatusFilter::Done) => todo.status == Status::Closed,
-                            Some(StatusFilter::Do) => todo.status == Status::Open,
+                            Some(filter) => filter.matches(todo.status),
                             None => true,
                         }
                 })
@@ -129,6 +128,7 @@ pub mod test {
                 prio: None,
                 tag: None,
                 due: Some(Datetime { timestamp: 0 }),
+                expr: None,
             },
         );
         assert!(err.is_err());
@@ -150,6 +150,7 @@ pub mod test {
                     prio: None,
                     tag: None,
                     due: Some(Datetime { timestamp: 0 }),
+                    expr: None,
                 },
             )
             .unwrap();
@@ -170,6 +171,7 @@ pub mod test {
                     prio: None,
                     tag: None,
                     due: Some(Datetime { timestamp: 0 }),
+                    expr: None,
                 },
             )
             .unwrap();
@@ -190,6 +192,7 @@ pub mod test {
                     prio: None,
                     tag: None,
                     due: Some(Datetime { timestamp: 0 }),
+                    expr: None,
                 },
             )
             .unwrap();
@@ -72,12 +72,10 @@ pub mod test {
                 .iter()
                 .filter(|todo| {
                     todo.tag == tag
-                        && match filters.status {
-                            Some(StatusFilter::All) => true,
-                            Some(StatusFilter::Done) => todo.status == Status::Closed,
-                            Some(StatusFilter::Do) => todo.status == Status::Open,
-                            None => true,
-                        }
+                        && filters
+                            .status
+                            .as_ref()
+                            .is_none_or(|status| status.matches(todo.status))
                 })
                 .cloned()
                 .collect::<Vec<TodoItem>>();
@@ -148,6 +146,7 @@ pub mod test {
                     prio: None,
                     tag: Some(Tag("should not be queried".to_string())),
                     due: None,
+                    expr: None,
                 },
             )
             .unwrap();
@@ -167,6 +166,7 @@ pub mod test {
                     prio: None,
                     tag: Some(Tag("should not be queried".to_string())),
                     due: None,
+                    expr: None,
                 },
             )
             .unwrap();
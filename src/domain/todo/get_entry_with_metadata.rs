@@ -19,7 +19,7 @@ pub mod test {
     use anyhow::bail;
     use std::{cell::RefCell, str::FromStr};
 
-    use crate::domain::{Datetime, Prio, Status, Tag, TodoItem};
+    use crate::domain::{Datetime, ListFilters, Prio, Status, Tag, TodoItem};
 
     struct FakeItemRepo {
         todos: RefCell<Vec<TodoItem>>,
@@ -63,15 +63,25 @@ pub mod test {
             let metadata = Metadata {
                 created_at: Datetime::from_str("13/06/2025")?,
                 last_updated: Datetime::from_str("13/06/2026")?,
+                number: 0,
+                list: "todo".to_string(),
             };
             Ok((todo_item, metadata))
         }
+
+        fn fetch_list_with_metadata(&self, _: ListFilters) -> Result<Vec<(TodoItem, Metadata)>> {
+            unreachable!()
+        }
     }
 
     impl TodoItemMetadata for FailingItemRepo {
         fn fetch_item_and_metadata(&self, _: &str) -> Result<(TodoItem, Metadata)> {
             bail!("Fake error while fetching entry with metadata")
         }
+
+        fn fetch_list_with_metadata(&self, _: ListFilters) -> Result<Vec<(TodoItem, Metadata)>> {
+            unreachable!()
+        }
     }
 
     #[test]
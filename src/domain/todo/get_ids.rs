@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+
+use crate::domain::{TodoItemQueryColumns, TodoList};
+
+impl TodoList {
+    pub fn get_ids(&self, repo: &impl TodoItemQueryColumns) -> Result<Vec<String>> {
+        repo.fetch_all_ids().context("✘ Couldn't fetch ids")
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use anyhow::bail;
+
+    use crate::domain::Tag;
+
+    struct FailingItemRepo;
+
+    impl TodoItemQueryColumns for FailingItemRepo {
+        fn fetch_tags(&self) -> Result<Vec<Tag>> {
+            unreachable!()
+        }
+
+        fn fetch_all_ids(&self) -> Result<Vec<String>> {
+            bail!("Fake error while fetching ids")
+        }
+    }
+
+    #[test]
+    fn should_provide_context_upon_failure() {
+        let repo = FailingItemRepo;
+        let todo_list = TodoList::new();
+        let result = todo_list.get_ids(&repo);
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.to_string(), "✘ Couldn't fetch ids");
+    }
+}
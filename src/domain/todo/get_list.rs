@@ -69,8 +69,7 @@ pub mod test {
                 .filter(|todo| match filters.status {
                     None => true,
                     Some(StatusFilter::All) => true,
-                    Some(StatusFilter::Do) => todo.status == Status::Open,
-                    Some(StatusFilter::Done) => todo.status == Status::Closed,
+                    Some(filter) => filter.matches(todo.status),
                 })
                 .cloned()
                 .collect();
@@ -112,6 +111,7 @@ pub mod test {
                     prio: None,
                     due: None,
                     tag: None,
+                    expr: None,
                 },
             )
             .unwrap();
@@ -131,6 +131,7 @@ pub mod test {
                     prio: None,
                     due: None,
                     tag: None,
+                    expr: None,
                 },
             )
             .unwrap();
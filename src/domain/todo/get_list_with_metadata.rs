@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+
+use crate::domain::{ListFilters, Metadata, TodoItem, TodoItemMetadata, TodoList};
+
+impl TodoList {
+    pub fn get_list_with_metadata(
+        &self,
+        repo: &impl TodoItemMetadata,
+        filters: ListFilters,
+    ) -> Result<Vec<(TodoItem, Metadata)>> {
+        repo.fetch_list_with_metadata(filters)
+            .context("✘ Couldn't fetch todos")
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use anyhow::bail;
+    use std::str::FromStr;
+
+    use crate::domain::{Datetime, Prio, Status, StatusFilter, Tag};
+
+    struct FakeItemRepo {
+        todos: Vec<(TodoItem, Metadata)>,
+    }
+
+    struct FailingItemRepo;
+
+    impl FakeItemRepo {
+        fn new() -> Result<Self> {
+            let metadata = Metadata {
+                created_at: Datetime::from_str("13/06/2025")?,
+                last_updated: Datetime::from_str("13/06/2026")?,
+                number: 0,
+                list: "todo".to_string(),
+            };
+            let todo_1 = TodoItem {
+                id: "todo-open".to_string(),
+                task: "task-1".to_string(),
+                due: Datetime::epoch(),
+                status: Status::Open,
+                prio: Prio::Empty,
+                tag: Tag::empty(),
+            };
+            let todo_2 = TodoItem {
+                id: "todo-closed".to_string(),
+                task: "task-2".to_string(),
+                due: Datetime::epoch(),
+                status: Status::Closed,
+                prio: Prio::Empty,
+                tag: Tag::empty(),
+            };
+            Ok(Self {
+                todos: vec![(todo_1, metadata.clone()), (todo_2, metadata)],
+            })
+        }
+    }
+
+    impl TodoItemMetadata for FakeItemRepo {
+        fn fetch_item_and_metadata(&self, _: &str) -> Result<(TodoItem, Metadata)> {
+            unreachable!()
+        }
+
+        fn fetch_list_with_metadata(
+            &self,
+            filters: ListFilters,
+        ) -> Result<Vec<(TodoItem, Metadata)>> {
+            Ok(self
+                .todos
+                .iter()
+                .filter(|(todo, _)| match filters.status {
+                    None | Some(StatusFilter::All) => true,
+                    Some(filter) => filter.matches(todo.status),
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    impl TodoItemMetadata for FailingItemRepo {
+        fn fetch_item_and_metadata(&self, _: &str) -> Result<(TodoItem, Metadata)> {
+            unreachable!()
+        }
+
+        fn fetch_list_with_metadata(&self, _: ListFilters) -> Result<Vec<(TodoItem, Metadata)>> {
+            bail!("Fake error while fetching list")
+        }
+    }
+
+    #[test]
+    fn should_provide_context_upon_failing() {
+        let repo = FailingItemRepo;
+        let todo_list = TodoList::new();
+        let err = todo_list.get_list_with_metadata(&repo, ListFilters::default());
+        assert!(err.is_err());
+        let err_msg = err.unwrap_err().to_string();
+        assert!(err_msg.contains("Couldn't fetch todos"));
+    }
+
+    #[test]
+    fn should_fetch_todos_with_metadata() -> Result<()> {
+        let repo = FakeItemRepo::new()?;
+        let todo_list = TodoList::new();
+        let filters = ListFilters {
+            status: Some(StatusFilter::Do),
+            ..ListFilters::default()
+        };
+        let entries = todo_list.get_list_with_metadata(&repo, filters)?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.id, "todo-open");
+        assert_eq!(entries[0].1.created_at, Datetime::from_str("13/06/2025")?);
+        Ok(())
+    }
+}
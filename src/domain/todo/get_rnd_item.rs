@@ -3,18 +3,19 @@ use anyhow::{Context, Result};
 use crate::domain::{ListFilters, Prio, StatusFilter, TodoItem, TodoItemRead, TodoList};
 
 impl TodoList {
-    pub fn get_rnd_item(&self, repo: &impl TodoItemRead) -> Result<Option<TodoItem>> {
+    pub fn get_rnd_item(&self, repo: &impl TodoItemRead, rnd: Prio) -> Result<Option<TodoItem>> {
         let todos = repo
             .fetch_list(ListFilters {
                 status: Some(StatusFilter::Do),
                 prio: None,
                 due: None,
                 tag: None,
+                expr: None,
             })
             .context("✘ Couldn't fetch todos while trying to retrieve a random todo")?;
         let rnd_todos: Vec<TodoItem> = todos
             .iter()
-            .filter(|todo| todo.prio == Prio::RND)
+            .filter(|todo| todo.prio == rnd)
             .cloned()
             .collect();
         if rnd_todos.is_empty() {
@@ -33,6 +34,8 @@ pub mod test {
 
     use crate::domain::{Datetime, Prio, Status, Tag, TodoItem};
 
+    const RND: Prio = Prio(Some(99));
+
     struct FakeItemRepo {
         todos: RefCell<Vec<TodoItem>>,
     }
@@ -47,7 +50,7 @@ pub mod test {
                 status: Status::Open,
                 due: Datetime::epoch(),
                 tag: Tag("some-tag-1".to_string()),
-                prio: Prio::RND,
+                prio: RND,
             };
             let todo_two = TodoItem {
                 id: "test-id-2".to_string(),
@@ -55,7 +58,7 @@ pub mod test {
                 status: Status::Open,
                 due: Datetime::epoch(),
                 tag: Tag("some-tag-2".to_string()),
-                prio: Prio::RND,
+                prio: RND,
             };
             let todo_three = TodoItem {
                 id: "test-id-3".to_string(),
@@ -63,7 +66,7 @@ pub mod test {
                 status: Status::Open,
                 due: Datetime::epoch(),
                 tag: Tag("some-tag-2".to_string()),
-                prio: Prio::RND,
+                prio: RND,
             };
             Self {
                 todos: RefCell::new(vec![todo_one, todo_two, todo_three]),
@@ -92,8 +95,7 @@ pub mod test {
                 .filter(|todo| match filters.status {
                     None => true,
                     Some(StatusFilter::All) => true,
-                    Some(StatusFilter::Do) => todo.status == Status::Open,
-                    Some(StatusFilter::Done) => todo.status == Status::Closed,
+                    Some(filter) => filter.matches(todo.status),
                 })
                 .cloned()
                 .collect();
@@ -117,7 +119,7 @@ pub mod test {
         assert_eq!(repo.len(), 3);
         let todos = repo.get_todos();
         let todo_list = TodoList::new();
-        let rnd_item: Option<TodoItem> = todo_list.get_rnd_item(&repo)?;
+        let rnd_item: Option<TodoItem> = todo_list.get_rnd_item(&repo, RND)?;
         assert!(rnd_item.is_some());
         assert!(todos.contains(&rnd_item.unwrap()));
         Ok(())
@@ -127,7 +129,7 @@ pub mod test {
     fn should_proide_context_upon_failure() {
         let repo = FailingItemRepo;
         let todo_list = TodoList::new();
-        let err = todo_list.get_rnd_item(&repo);
+        let err = todo_list.get_rnd_item(&repo, RND);
         assert!(err.is_err());
         let err_msg = err.unwrap_err().to_string();
         assert!(err_msg.contains("Couldn't fetch todos while trying to retrieve a random todo"));
@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
 
-use crate::domain::{ListFilters, StatusFilter, TodoItem, TodoItemRead, TodoList};
+use crate::domain::{
+    FilterExpr, ListFilters, Metadata, StatusFilter, TodoItem, TodoItemMetadata, TodoItemRead,
+    TodoList,
+};
 
 #[derive(Clone, Debug)]
 pub struct GrepOptions {
     pub case_insensitive: bool,
+    pub filter: Option<FilterExpr>,
 }
 
 impl TodoList {
@@ -20,16 +24,39 @@ impl TodoList {
                 prio: None,
                 due: None,
                 tag: None,
+                expr: options.filter.clone(),
             })
             .context("✘ Couldn't fetch todos while searching for pattern '{pattern}'")?;
-        if options.case_insensitive {
-            let pattern = pattern.to_string().to_lowercase();
-            todos.retain(|todo| todo.task.to_lowercase().contains(&pattern))
-        } else {
-            todos.retain(|todo| todo.task.contains(pattern))
-        };
+        todos.retain(|todo| matches_pattern(&todo.task, pattern, &options));
         Ok(todos)
     }
+
+    pub fn grep_with_metadata(
+        &self,
+        repo: &impl TodoItemMetadata,
+        pattern: &str,
+        options: GrepOptions,
+    ) -> Result<Vec<(TodoItem, Metadata)>> {
+        let mut entries = repo
+            .fetch_list_with_metadata(ListFilters {
+                status: Some(StatusFilter::All),
+                prio: None,
+                due: None,
+                tag: None,
+                expr: options.filter.clone(),
+            })
+            .context("✘ Couldn't fetch todos while searching for pattern '{pattern}'")?;
+        entries.retain(|(todo, _)| matches_pattern(&todo.task, pattern, &options));
+        Ok(entries)
+    }
+}
+
+fn matches_pattern(task: &str, pattern: &str, options: &GrepOptions) -> bool {
+    if options.case_insensitive {
+        task.to_lowercase().contains(&pattern.to_lowercase())
+    } else {
+        task.contains(pattern)
+    }
 }
 
 pub fn search_in_task(pattern: &str, item: &TodoItem) -> bool {
@@ -86,6 +113,27 @@ pub mod tests {
         }
     }
 
+    impl TodoItemMetadata for FakeItemRepo {
+        fn fetch_item_and_metadata(&self, _: &str) -> Result<(TodoItem, Metadata)> {
+            unreachable!()
+        }
+
+        fn fetch_list_with_metadata(&self, _: ListFilters) -> Result<Vec<(TodoItem, Metadata)>> {
+            let metadata = Metadata {
+                created_at: Datetime::epoch(),
+                last_updated: Datetime::epoch(),
+                number: 0,
+                list: "todo".to_string(),
+            };
+            Ok(self
+                .todos
+                .borrow()
+                .iter()
+                .map(|todo| (todo.clone(), metadata.clone()))
+                .collect())
+        }
+    }
+
     impl TodoItemRead for FailingItemRepo {
         fn fetch_item(&self, _: &str) -> Result<TodoItem> {
             unreachable!()
@@ -102,6 +150,7 @@ pub mod tests {
         let todo_list = TodoList::new();
         let options = GrepOptions {
             case_insensitive: false,
+            filter: None,
         };
         let err = todo_list.grep(&repo, "any pattern", options);
         assert!(err.is_err());
@@ -116,6 +165,7 @@ pub mod tests {
         let todo_list = TodoList::new();
         let options = GrepOptions {
             case_insensitive: false,
+            filter: None,
         };
         let todos_miss = todo_list.grep(&repo, miss, options)?;
         assert!(todos_miss.is_empty());
@@ -130,6 +180,7 @@ pub mod tests {
         let todo_list = TodoList::new();
         let options = GrepOptions {
             case_insensitive: false,
+            filter: None,
         };
         let todos_match = todo_list.grep(&repo, pattern_long, options.clone())?;
         assert_eq!(todos_match.len(), 1);
@@ -144,9 +195,24 @@ pub mod tests {
         let todo_list = TodoList::new();
         let options = GrepOptions {
             case_insensitive: true,
+            filter: None,
         };
         let todos_match = todo_list.grep(&repo, pattern_long, options)?;
         assert_eq!(todos_match.len(), 1);
         Ok(())
     }
+
+    #[test]
+    fn should_return_entries_with_metadata_containing_pattern() -> Result<()> {
+        let repo = FakeItemRepo::new();
+        let todo_list = TodoList::new();
+        let options = GrepOptions {
+            case_insensitive: true,
+            filter: None,
+        };
+        let entries = todo_list.grep_with_metadata(&repo, "SHORT", options)?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.id, "id-short");
+        Ok(())
+    }
 }
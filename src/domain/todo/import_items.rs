@@ -0,0 +1,243 @@
+use anyhow::{bail, Context, Result};
+
+use crate::domain::{
+    Datetime, Prio, Status, Tag, TodoItem, TodoItemCreate, TodoItemExternalRef, TodoItemUpdate,
+    TodoList,
+};
+
+#[derive(Clone, Debug)]
+pub struct ExternalItem {
+    pub external_ref: String,
+    pub task: String,
+    pub tag: Tag,
+    pub due: Datetime,
+    pub status: Option<Status>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+}
+
+impl TodoList {
+    pub fn import_items<R>(
+        &self,
+        repo: &R,
+        items: Vec<ExternalItem>,
+        mut new_id: impl FnMut() -> String,
+    ) -> Result<ImportSummary>
+    where
+        R: TodoItemCreate + TodoItemUpdate + TodoItemExternalRef,
+    {
+        let mut summary = ImportSummary::default();
+        for external in items {
+            if external.external_ref.is_empty() {
+                bail!("✘ Can't import an item without external reference")
+            }
+            let existing = repo
+                .fetch_id_by_external_ref(&external.external_ref)
+                .context(format!(
+                    "✘ Couldn't look up external reference '{}'",
+                    external.external_ref
+                ))?;
+            match existing {
+                Some(id) => {
+                    log::info!("updating item {id} from {}", external.external_ref);
+                    repo.update_task(&external.task, &id)?;
+                    repo.update(
+                        Some(external.due),
+                        None,
+                        external.status,
+                        Some(external.tag),
+                        vec![id],
+                    )
+                    .context(format!(
+                        "✘ Couldn't update item imported from '{}'",
+                        external.external_ref
+                    ))?;
+                    summary.updated += 1;
+                }
+                None => {
+                    let item = TodoItem {
+                        id: new_id(),
+                        task: external.task,
+                        status: external.status.unwrap_or(Status::Open),
+                        prio: Prio::Empty,
+                        due: external.due,
+                        tag: external.tag,
+                    };
+                    log::info!("creating item {} from {}", item.id, external.external_ref);
+                    self.add_item(repo, &item)?;
+                    repo.set_external_ref(&item.id, &external.external_ref)?;
+                    summary.created += 1;
+                }
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct FakeItemRepo {
+        todos: RefCell<Vec<(TodoItem, Option<String>)>>,
+    }
+
+    impl FakeItemRepo {
+        fn new() -> Self {
+            Self {
+                todos: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn len(&self) -> usize {
+            self.todos.borrow().len()
+        }
+
+        fn get_todos(&self) -> Vec<TodoItem> {
+            self.todos
+                .borrow()
+                .iter()
+                .map(|(item, _)| item.clone())
+                .collect()
+        }
+    }
+
+    impl TodoItemCreate for FakeItemRepo {
+        fn add(&self, item: &TodoItem) -> Result<()> {
+            self.todos.borrow_mut().push((item.clone(), None));
+            Ok(())
+        }
+    }
+
+    impl TodoItemUpdate for FakeItemRepo {
+        fn update_task(&self, task: &str, id: &str) -> Result<()> {
+            for (todo, _) in self.todos.borrow_mut().iter_mut() {
+                if todo.id == id {
+                    todo.task = task.to_string();
+                }
+            }
+            Ok(())
+        }
+
+        fn update(
+            &self,
+            due: Option<Datetime>,
+            _: Option<Prio>,
+            status: Option<Status>,
+            tag: Option<Tag>,
+            ids: Vec<String>,
+        ) -> Result<()> {
+            for (todo, _) in self.todos.borrow_mut().iter_mut() {
+                if ids.contains(&todo.id) {
+                    if let Some(due) = due {
+                        todo.due = due;
+                    }
+                    if let Some(status) = status {
+                        todo.status = status;
+                    }
+                    if let Some(tag) = tag.clone() {
+                        todo.tag = tag;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        fn close_all(&self, _: Option<Prio>) -> Result<()> {
+            unreachable!()
+        }
+    }
+
+    impl TodoItemExternalRef for FakeItemRepo {
+        fn fetch_id_by_external_ref(&self, external_ref: &str) -> Result<Option<String>> {
+            Ok(self
+                .todos
+                .borrow()
+                .iter()
+                .find(|(_, reference)| reference.as_deref() == Some(external_ref))
+                .map(|(todo, _)| todo.id.clone()))
+        }
+
+        fn set_external_ref(&self, id: &str, external_ref: &str) -> Result<()> {
+            for (todo, reference) in self.todos.borrow_mut().iter_mut() {
+                if todo.id == id {
+                    *reference = Some(external_ref.to_string());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn external_item(external_ref: &str, task: &str) -> ExternalItem {
+        ExternalItem {
+            external_ref: external_ref.to_string(),
+            task: task.to_string(),
+            tag: Tag("bug".to_string()),
+            due: Datetime::epoch(),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn should_create_new_items() -> Result<()> {
+        let repo = FakeItemRepo::new();
+        let todo_list = TodoList::new();
+        let mut counter = 0;
+        let items = vec![
+            external_item("https://github.com/o/r/issues/1", "issue-1"),
+            external_item("https://github.com/o/r/issues/2", "issue-2"),
+        ];
+        let summary = todo_list.import_items(&repo, items, || {
+            counter += 1;
+            format!("id-{counter}")
+        })?;
+        assert_eq!(
+            summary,
+            ImportSummary {
+                created: 2,
+                updated: 0
+            }
+        );
+        assert_eq!(repo.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn should_update_items_on_reimport() -> Result<()> {
+        let repo = FakeItemRepo::new();
+        let todo_list = TodoList::new();
+        let items = vec![external_item("https://github.com/o/r/issues/1", "old")];
+        todo_list.import_items(&repo, items, || "id-1".to_string())?;
+        let mut changed = external_item("https://github.com/o/r/issues/1", "new");
+        changed.status = Some(Status::Closed);
+        let summary = todo_list.import_items(&repo, vec![changed], || "id-2".to_string())?;
+        assert_eq!(
+            summary,
+            ImportSummary {
+                created: 0,
+                updated: 1
+            }
+        );
+        assert_eq!(repo.len(), 1);
+        let todo = &repo.get_todos()[0];
+        assert_eq!(todo.id, "id-1");
+        assert_eq!(todo.task, "new");
+        assert_eq!(todo.status, Status::Closed);
+        Ok(())
+    }
+
+    #[test]
+    fn should_err_on_missing_external_ref() {
+        let repo = FakeItemRepo::new();
+        let todo_list = TodoList::new();
+        let items = vec![external_item("", "issue")];
+        assert!(todo_list
+            .import_items(&repo, items, || "id".to_string())
+            .is_err());
+    }
+}
@@ -9,10 +9,18 @@ pub mod get_entries_by_due_date;
 pub mod get_entries_by_tag;
 pub mod get_entry;
 pub mod get_entry_with_metadata;
+pub mod get_ids;
 pub mod get_list;
+pub mod get_list_with_metadata;
 pub mod get_rnd_item;
 pub mod get_tags;
 pub mod grep;
+pub mod import_items;
+pub mod selection;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod transfer_items;
 pub mod update;
+pub mod update_list;
 
 pub use entities::*;
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+
+use crate::domain::{Selection, SelectionRepository, TodoList};
+
+impl TodoList {
+    pub fn get_selection(&self, repo: &impl SelectionRepository) -> Result<Selection> {
+        repo.fetch_selection()
+            .context("✘ Couldn't read the loaded list")
+    }
+
+    pub fn select_list(&self, repo: &impl SelectionRepository, list: &str) -> Result<()> {
+        let selection = self.get_selection(repo)?;
+        let previous = match selection.current {
+            Some(current) if current != list => Some(current),
+            _ => selection.previous,
+        };
+        let selection = Selection {
+            current: Some(list.to_string()),
+            previous,
+        };
+        repo.save_selection(&selection)
+            .context("✘ Couldn't load list")
+    }
+
+    pub fn import_selection(
+        &self,
+        repo: &impl SelectionRepository,
+        selection: &Selection,
+    ) -> Result<()> {
+        if self.get_selection(repo)?.current.is_some() {
+            log::info!("ignoring imported selection {selection:?}");
+            return Ok(());
+        }
+        repo.save_selection(selection)
+            .context("✘ Couldn't import the loaded list")
+    }
+
+    pub fn deselect_list(&self, repo: &impl SelectionRepository, list: &str) -> Result<()> {
+        let selection = self.get_selection(repo)?;
+        let keep = |name: Option<String>| name.filter(|name| name != list);
+        let selection = Selection {
+            current: keep(selection.current),
+            previous: keep(selection.previous),
+        };
+        repo.save_selection(&selection)
+            .context("✘ Couldn't update the loaded list")
+    }
+
+    pub fn rename_selection(
+        &self,
+        repo: &impl SelectionRepository,
+        list: &str,
+        new_name: &str,
+    ) -> Result<()> {
+        let selection = self.get_selection(repo)?;
+        let rename = |name: Option<String>| {
+            name.map(|name| match name == list {
+                true => new_name.to_string(),
+                false => name,
+            })
+        };
+        let selection = Selection {
+            current: rename(selection.current),
+            previous: rename(selection.previous),
+        };
+        repo.save_selection(&selection)
+            .context("✘ Couldn't update the loaded list")
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use anyhow::bail;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct FakeSelectionRepo {
+        selection: RefCell<Selection>,
+    }
+
+    impl SelectionRepository for FakeSelectionRepo {
+        fn fetch_selection(&self) -> Result<Selection> {
+            Ok(self.selection.borrow().clone())
+        }
+
+        fn save_selection(&self, selection: &Selection) -> Result<()> {
+            *self.selection.borrow_mut() = selection.clone();
+            Ok(())
+        }
+    }
+
+    struct FailingRepo;
+
+    impl SelectionRepository for FailingRepo {
+        fn fetch_selection(&self) -> Result<Selection> {
+            bail!("Fake error while fetching selection")
+        }
+
+        fn save_selection(&self, _selection: &Selection) -> Result<()> {
+            unreachable!()
+        }
+    }
+
+    fn selection(current: Option<&str>, previous: Option<&str>) -> Selection {
+        Selection {
+            current: current.map(str::to_string),
+            previous: previous.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn should_remember_previous_list() -> Result<()> {
+        let repo = FakeSelectionRepo::default();
+        let todo_list = TodoList::new();
+        todo_list.select_list(&repo, "todo")?;
+        assert_eq!(repo.fetch_selection()?, selection(Some("todo"), None));
+        todo_list.select_list(&repo, "work")?;
+        assert_eq!(
+            repo.fetch_selection()?,
+            selection(Some("work"), Some("todo"))
+        );
+        // loading the same list again keeps the previous one
+        todo_list.select_list(&repo, "work")?;
+        assert_eq!(
+            repo.fetch_selection()?,
+            selection(Some("work"), Some("todo"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_forget_deselected_list() -> Result<()> {
+        let repo = FakeSelectionRepo {
+            selection: RefCell::new(selection(Some("work"), Some("todo"))),
+        };
+        let todo_list = TodoList::new();
+        todo_list.deselect_list(&repo, "todo")?;
+        assert_eq!(repo.fetch_selection()?, selection(Some("work"), None));
+        Ok(())
+    }
+
+    #[test]
+    fn should_follow_renamed_list() -> Result<()> {
+        let repo = FakeSelectionRepo {
+            selection: RefCell::new(selection(Some("work"), Some("todo"))),
+        };
+        let todo_list = TodoList::new();
+        todo_list.rename_selection(&repo, "todo", "home")?;
+        assert_eq!(
+            repo.fetch_selection()?,
+            selection(Some("work"), Some("home"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_import_selection_only_if_none_is_loaded() -> Result<()> {
+        let repo = FakeSelectionRepo::default();
+        let todo_list = TodoList::new();
+        todo_list.import_selection(&repo, &selection(Some("work"), Some("todo")))?;
+        assert_eq!(
+            repo.fetch_selection()?,
+            selection(Some("work"), Some("todo"))
+        );
+        todo_list.import_selection(&repo, &selection(Some("home"), None))?;
+        assert_eq!(
+            repo.fetch_selection()?,
+            selection(Some("work"), Some("todo"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_provide_context_upon_failure() {
+        let todo_list = TodoList::new();
+        let err = todo_list.select_list(&FailingRepo, "todo").unwrap_err();
+        assert_eq!(err.to_string(), "✘ Couldn't read the loaded list");
+    }
+}
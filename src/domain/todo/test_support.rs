@@ -0,0 +1,70 @@
+use crate::domain::{Datetime, Metadata, Prio, Status, Tag, TodoItem};
+
+// an open task of the list `todo` without prio, due date or tag, created at the epoch
+pub(crate) fn entry(id: &str) -> EntryBuilder {
+    EntryBuilder(
+        TodoItem {
+            id: id.to_string(),
+            task: format!("Task {id}"),
+            status: Status::Open,
+            prio: Prio::Empty,
+            due: Datetime::epoch(),
+            tag: Tag::empty(),
+        },
+        Metadata {
+            created_at: Datetime::epoch(),
+            last_updated: Datetime::epoch(),
+            number: 0,
+            list: "todo".to_string(),
+        },
+    )
+}
+
+pub(crate) struct EntryBuilder(TodoItem, Metadata);
+
+impl EntryBuilder {
+    pub(crate) fn task(mut self, task: &str) -> Self {
+        self.0.task = task.to_string();
+        self
+    }
+
+    pub(crate) fn status(mut self, status: Status) -> Self {
+        self.0.status = status;
+        self
+    }
+
+    pub(crate) fn prio(mut self, prio: Prio) -> Self {
+        self.0.prio = prio;
+        self
+    }
+
+    pub(crate) fn due(mut self, due: Datetime) -> Self {
+        self.0.due = due;
+        self
+    }
+
+    pub(crate) fn tag(mut self, tag: &str) -> Self {
+        self.0.tag = Tag(tag.to_string());
+        self
+    }
+
+    pub(crate) fn created(mut self, created_at: Datetime) -> Self {
+        self.1.created_at = created_at;
+        self.1.last_updated = created_at;
+        self
+    }
+
+    pub(crate) fn updated(mut self, last_updated: Datetime) -> Self {
+        self.1.last_updated = last_updated;
+        self
+    }
+
+    pub(crate) fn number(mut self, number: i64) -> Self {
+        self.1.number = number;
+        self
+    }
+
+    pub(crate) fn build(self) -> (TodoItem, Metadata) {
+        (self.0, self.1)
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{bail, Context, Result};
+
+use crate::domain::{TodoItemTransfer, TodoList};
+
+impl TodoList {
+    pub fn move_items(
+        &self,
+        repo: &impl TodoItemTransfer,
+        source: &str,
+        target: &str,
+        ids: Vec<String>,
+    ) -> Result<()> {
+        if source == target {
+            bail!("✘ The tasks are in '{target}' already");
+        }
+        let ids: Vec<(String, String)> = ids.into_iter().map(|id| (id.clone(), id)).collect();
+        repo.transfer(target, &ids, true)
+            .context(format!("✘ Couldn't move tasks to '{target}'"))
+    }
+
+    pub fn copy_items(
+        &self,
+        repo: &impl TodoItemTransfer,
+        target: &str,
+        ids: Vec<String>,
+        mut new_id: impl FnMut() -> String,
+    ) -> Result<Vec<String>> {
+        let ids: Vec<(String, String)> = ids.into_iter().map(|id| (id, new_id())).collect();
+        repo.transfer(target, &ids, false)
+            .context(format!("✘ Couldn't copy tasks to '{target}'"))?;
+        Ok(ids.into_iter().map(|(_, new_id)| new_id).collect())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Debug, PartialEq)]
+    struct Transfer {
+        target: String,
+        ids: Vec<(String, String)>,
+        remove: bool,
+    }
+
+    #[derive(Default)]
+    struct FakeItemRepo {
+        transfers: RefCell<Vec<Transfer>>,
+    }
+
+    impl TodoItemTransfer for FakeItemRepo {
+        fn transfer(&self, target: &str, ids: &[(String, String)], remove: bool) -> Result<()> {
+            self.transfers.borrow_mut().push(Transfer {
+                target: target.to_string(),
+                ids: ids.to_vec(),
+                remove,
+            });
+            Ok(())
+        }
+    }
+
+    struct FailingRepo;
+
+    impl TodoItemTransfer for FailingRepo {
+        fn transfer(&self, _: &str, _: &[(String, String)], _: bool) -> Result<()> {
+            bail!("Fake error while transferring tasks")
+        }
+    }
+
+    #[test]
+    fn should_move_items_keeping_their_ids() -> Result<()> {
+        let repo = FakeItemRepo::default();
+        let todo_list = TodoList::new();
+        todo_list.move_items(&repo, "inbox", "work", vec!["a".to_string()])?;
+        assert_eq!(
+            repo.transfers.borrow()[0],
+            Transfer {
+                target: "work".to_string(),
+                ids: vec![("a".to_string(), "a".to_string())],
+                remove: true,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_copy_items_with_new_ids() -> Result<()> {
+        let repo = FakeItemRepo::default();
+        let todo_list = TodoList::new();
+        let mut counter = 0;
+        let new_ids = todo_list.copy_items(
+            &repo,
+            "work",
+            vec!["a".to_string(), "b".to_string()],
+            || {
+                counter += 1;
+                format!("new-{counter}")
+            },
+        )?;
+        assert_eq!(new_ids, vec!["new-1", "new-2"]);
+        assert!(!repo.transfers.borrow()[0].remove);
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_to_move_within_a_list() {
+        let todo_list = TodoList::new();
+        let err = todo_list
+            .move_items(&FakeItemRepo::default(), "work", "work", vec![])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "✘ The tasks are in 'work' already");
+    }
+
+    #[test]
+    fn should_provide_context_upon_failure() {
+        let todo_list = TodoList::new();
+        let err = todo_list
+            .copy_items(&FailingRepo, "work", vec!["a".to_string()], String::new)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "✘ Couldn't copy tasks to 'work'");
+    }
+}
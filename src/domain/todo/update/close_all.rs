@@ -5,7 +5,10 @@ use crate::domain::{Prio, TodoItemUpdate, TodoList};
 impl TodoList {
     pub fn close_all(&self, repo: &impl TodoItemUpdate, prio: Option<Prio>) -> Result<()> {
         let err_context = match prio {
-            Some(prio) => format!("✘ Couldn't close all todos with prio {}", prio),
+            Some(Prio(Some(rank))) => {
+                format!("✘ Couldn't close all todos with prio rank {rank}")
+            }
+            Some(Prio(None)) => "✘ Couldn't close all todos without prio".to_string(),
             None => "✘ Couldn't close all todos".to_string(),
         };
         repo.close_all(prio).context(err_context)?;
@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+
+use crate::domain::{TodoList, TodoListRead, TodoListUpdate};
+
+impl TodoList {
+    pub fn rename_list<R>(&self, repo: &R, list: &str, new_name: &str) -> Result<()>
+    where
+        R: TodoListRead + TodoListUpdate,
+    {
+        self.check_list_name(repo, new_name)?;
+        repo.rename(list, new_name)
+            .context(format!("✘ Couldn't rename list '{list}'"))
+    }
+
+    pub fn update_list(
+        &self,
+        repo: &impl TodoListUpdate,
+        list: &str,
+        description: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<()> {
+        repo.update_details(list, description, color)
+            .context(format!("✘ Couldn't update list '{list}'"))
+    }
+
+    pub fn archive_list(&self, repo: &impl TodoListUpdate, list: &str) -> Result<()> {
+        repo.set_archived(list, true)
+            .context(format!("✘ Couldn't archive list '{list}'"))
+    }
+
+    pub fn unarchive_list(&self, repo: &impl TodoListUpdate, list: &str) -> Result<()> {
+        repo.set_archived(list, false)
+            .context(format!("✘ Couldn't unarchive list '{list}'"))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use anyhow::bail;
+    use std::cell::RefCell;
+
+    use crate::domain::ListDetails;
+
+    struct FakeListRepo {
+        lists: RefCell<Vec<ListDetails>>,
+    }
+
+    impl FakeListRepo {
+        fn new() -> Self {
+            let list = |name: &str| ListDetails {
+                name: name.to_string(),
+                ..ListDetails::default()
+            };
+            Self {
+                lists: RefCell::new(vec![list("todo"), list("work")]),
+            }
+        }
+    }
+
+    impl TodoListRead for FakeListRepo {
+        fn fetch_all(&self) -> Result<Vec<String>> {
+            Ok(self
+                .lists
+                .borrow()
+                .iter()
+                .map(|list| list.name.clone())
+                .collect())
+        }
+
+        fn fetch_id(&self, _: &str) -> Result<i64> {
+            unreachable!()
+        }
+
+        fn fetch_details(&self) -> Result<Vec<ListDetails>> {
+            Ok(self.lists.borrow().clone())
+        }
+    }
+
+    impl TodoListUpdate for FakeListRepo {
+        fn rename(&self, list_name: &str, new_name: &str) -> Result<()> {
+            let mut lists = self.lists.borrow_mut();
+            let Some(list) = lists.iter_mut().find(|list| list.name == list_name) else {
+                bail!("Fake error, no list '{list_name}'")
+            };
+            list.name = new_name.to_string();
+            Ok(())
+        }
+
+        fn update_details(&self, _: &str, _: Option<&str>, _: Option<&str>) -> Result<()> {
+            unreachable!()
+        }
+
+        fn set_archived(&self, list_name: &str, archived: bool) -> Result<()> {
+            let mut lists = self.lists.borrow_mut();
+            let Some(list) = lists.iter_mut().find(|list| list.name == list_name) else {
+                bail!("Fake error, no list '{list_name}'")
+            };
+            list.archived = archived;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_rename_list() -> Result<()> {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        todo_list.rename_list(&repo, "work", "job")?;
+        assert_eq!(repo.fetch_all()?, vec!["todo", "job"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_to_rename_to_existing_list() {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        let err = todo_list.rename_list(&repo, "work", "todo").unwrap_err();
+        assert_eq!(err.to_string(), "✘ There is a list named 'todo' already");
+    }
+
+    #[test]
+    fn should_refuse_to_rename_to_empty_name() {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        let err = todo_list.rename_list(&repo, "work", "").unwrap_err();
+        assert_eq!(err.to_string(), "✘ A list needs a name");
+    }
+
+    #[test]
+    fn should_archive_and_unarchive_list() -> Result<()> {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        todo_list.archive_list(&repo, "work")?;
+        assert_eq!(todo_list.get_active_lists(&repo)?, vec!["todo"]);
+        todo_list.unarchive_list(&repo, "work")?;
+        assert_eq!(todo_list.get_active_lists(&repo)?, vec!["todo", "work"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_provide_context_upon_failure() {
+        let repo = FakeListRepo::new();
+        let todo_list = TodoList::new();
+        let err = todo_list.archive_list(&repo, "home").unwrap_err();
+        assert_eq!(err.to_string(), "✘ Couldn't archive list 'home'");
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+
+use crate::domain::{Metadata, Prio, PrioLevel, TodoItem};
+use crate::util::parse_task;
+
+// Field names are part of the machine-readable output and must stay stable.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TodoItemView {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    pub status: String,
+    pub prio: Option<String>,
+    pub due: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MetadataView {
+    pub created_at: String,
+    pub last_updated: String,
+    pub number: i64,
+    pub list: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TodoEntryView {
+    #[serde(flatten)]
+    pub item: TodoItemView,
+    #[serde(flatten)]
+    pub metadata: MetadataView,
+}
+
+impl TodoItemView {
+    pub fn new(item: &TodoItem, levels: &[PrioLevel]) -> Self {
+        let (title, body) = parse_task(&item.task);
+        let prio = match item.prio {
+            Prio::Empty => None,
+            prio => Some(prio.name(levels)),
+        };
+        let tag = if item.tag.0.is_empty() {
+            None
+        } else {
+            Some(item.tag.0.clone())
+        };
+        Self {
+            id: item.id.clone(),
+            title,
+            body,
+            status: item.status.name().to_string(),
+            prio,
+            due: item.due.to_iso_date(),
+            tag,
+        }
+    }
+}
+
+impl From<&Metadata> for MetadataView {
+    fn from(metadata: &Metadata) -> Self {
+        Self {
+            created_at: metadata.created_at.to_rfc3339(),
+            last_updated: metadata.last_updated.to_rfc3339(),
+            number: metadata.number,
+            list: metadata.list.clone(),
+        }
+    }
+}
+
+impl TodoEntryView {
+    pub fn new(item: &TodoItem, metadata: &Metadata, levels: &[PrioLevel]) -> Self {
+        Self {
+            item: TodoItemView::new(item, levels),
+            metadata: MetadataView::from(metadata),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::{Datetime, Status, Tag};
+
+    #[test]
+    fn should_map_empty_columns_to_none() {
+        let item = TodoItem {
+            id: "test-id".to_string(),
+            task: "Title\n\nBody".to_string(),
+            status: Status::Open,
+            prio: Prio::Empty,
+            due: Datetime::epoch(),
+            tag: Tag::empty(),
+        };
+        let view = TodoItemView::new(&item, &PrioLevel::defaults());
+        assert_eq!(view.title, "Title");
+        assert_eq!(view.body, "Body");
+        assert_eq!(view.status, "open");
+        assert_eq!(view.prio, None);
+        assert_eq!(view.due, None);
+        assert_eq!(view.tag, None);
+    }
+
+    #[test]
+    fn should_keep_field_order_stable() -> anyhow::Result<()> {
+        let item = TodoItem {
+            id: "test-id".to_string(),
+            task: "Title".to_string(),
+            status: Status::Closed,
+            prio: Prio::P1,
+            due: Datetime::epoch(),
+            tag: Tag("work".to_string()),
+        };
+        let json = serde_json::to_string(&TodoItemView::new(&item, &PrioLevel::defaults()))?;
+        assert_eq!(
+            json,
+            r#"{"id":"test-id","title":"Title","body":"","status":"closed","prio":"P1","due":null,"tag":"work"}"#
+        );
+        Ok(())
+    }
+}
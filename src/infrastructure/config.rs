@@ -2,8 +2,9 @@ use anyhow::{anyhow, Context, Result};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table};
 
-use crate::application::config::Config;
+use crate::application::config::{Config, ViewConfig};
 use crate::application::editor::Editor;
 use crate::infrastructure::paths::UserPaths;
 
@@ -29,17 +30,74 @@ todo_db = "{}"
 
 [style]
 id_length = 6
+id_mode = "id" # id | number: show sequential numbers per list instead of id prefixes
 due_date_display_format = "%x" # chrono strftime-style
 due_date_input_format = "DMY" # MDY | ISO
 show_due = true
 show_tag = true
-sort_by = "prio"  # prio | due | tag
+due_horizon = 7 # show due dates within this many days as "in 5d"
+sort_by = "prio,due"  # comma separated: id | prio | tag | due | created | updated | title | status, "-" prefix reverses
 table = "modern_rounded" # ascii | ascii_rounded | modern |  modern_rounded | markdown
+# group_by = "tag" # tag | prio | due | list | status
+# columns = ["id", "prio", "title", "due", "tag"] # also: number | status | created | updated | age | urgency
+
+[theme] # color names like "red" or "bright blue", "" for no color
+id = "yellow"
+tag = ""
+done = "green"
+in_progress = "cyan"
+blocked = "magenta"
+cancelled = "bright black"
+overdue = "red"
+
+[board] # `todo board`
+# group_by = "status" # status | tag | prio | due
+# columns = ["In progress", "Open", "Blocked", "Done"]
+# limit = 10
+
+[board.wip] # maximum number of tasks per column
+# "In progress" = 3
 
 [aliases]
-p1 = "add --prio=p1",
-p2 = "add --prio=p2",
-p3 = "add --prio=p3"#,
+p1 = "add --prio=p1"
+p2 = "add --prio=p2"
+p3 = "add --prio=p3"
+
+[templates] # use with `todo list --format <name>`
+short = "{{id:8}} {{prio:2}} {{title:.50}}"
+bar = "{{prio}} {{title:.30}} {{due:%d.%m}}"
+
+# saved list flags, use with `todo list --view <name>` or `todo view <name>`
+# [views.standup]
+# where = "status:in-progress or (prio:p1 and due<=today)"
+# sort = "prio,due"
+# group_by = "tag"
+# columns = ["id", "prio", "title", "due"]
+
+# priority levels, lower ranks first; the rank is stored with each task
+[[priorities]]
+name = "P1"
+rank = 1
+aliases = ["1", "high"]
+color = "red"
+
+[[priorities]]
+name = "P2"
+rank = 2
+aliases = ["2", "mid"]
+color = "yellow"
+
+[[priorities]]
+name = "P3"
+rank = 3
+aliases = ["3", "low"]
+color = "green"
+
+[[priorities]]
+name = "RND" # `todo rnd` picks among the tasks of the level named or aliased "rnd"
+rank = 99
+color = "purple"
+"#,
             db_path.to_string_lossy()
         )
         .context("✘ Couldn't write default config to file")?;
@@ -75,6 +133,30 @@ pub fn edit_config(editor: &impl Editor) -> Result<()> {
     }
 }
 
+pub fn save_view(user_paths: &UserPaths, name: &str, view: &ViewConfig) -> Result<()> {
+    let path = get_todo_config(user_paths)?;
+    let config = fs::read_to_string(&path).context("✘ Couldn't read file")?;
+    let config = set_view(&config, name, view)?;
+    fs::write(&path, config).context("✘ Couldn't write config to file")
+}
+
+// edits the document in place, so that the rest of the file keeps its formatting and comments
+fn set_view(config: &str, name: &str, view: &ViewConfig) -> Result<String> {
+    let mut document: DocumentMut = config.parse().context("✘ Couldn't parse toml")?;
+    let views = document
+        .entry("views")
+        .or_insert_with(|| {
+            let mut views = Table::new();
+            views.set_implicit(true);
+            Item::Table(views)
+        })
+        .as_table_mut()
+        .ok_or(anyhow!("✘ 'views' in the config isn't a table"))?;
+    let view: DocumentMut = toml::to_string(view)?.parse()?;
+    views.insert(name, Item::Table(view.as_table().clone()));
+    Ok(document.to_string())
+}
+
 pub fn get_config(user_paths: &UserPaths) -> Result<PathBuf> {
     if let Some(ref path) = user_paths.config {
         Ok(path.clone())
@@ -90,3 +172,70 @@ pub fn get_todo_config(user_paths: &UserPaths) -> Result<PathBuf> {
         Err(anyhow!("✘ No configuration file found"))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::config::Theme;
+    use crate::domain::{Column, GroupBy, StatusFilter};
+
+    const CONFIG: &str = r#"[style]
+table = "modern" # keep me
+
+[views.standup]
+where = "prio:p1"
+"#;
+
+    fn views(config: &str) -> toml::Table {
+        let config: toml::Table = toml::from_str(config).unwrap();
+        config["views"].as_table().unwrap().clone()
+    }
+
+    #[test]
+    fn should_add_view_and_keep_comments() -> Result<()> {
+        let view = ViewConfig {
+            filter: Some("tag:backend and due<eow".to_string()),
+            status: Some(StatusFilter::InProgress),
+            group_by: Some(GroupBy::Prio),
+            columns: Some(vec![Column::Id, Column::Title]),
+            ..ViewConfig::default()
+        };
+        let config = set_view(CONFIG, "week", &view)?;
+        assert!(config.contains(r#"table = "modern" # keep me"#));
+        let views = views(&config);
+        assert!(views.contains_key("standup"));
+        let week: ViewConfig = views["week"].clone().try_into()?;
+        assert_eq!(week, view);
+        Ok(())
+    }
+
+    #[test]
+    fn should_replace_view_of_same_name() -> Result<()> {
+        let view = ViewConfig {
+            sort: Some("-due".to_string()),
+            ..ViewConfig::default()
+        };
+        let config = set_view(CONFIG, "standup", &view)?;
+        let standup: ViewConfig = views(&config)["standup"].clone().try_into()?;
+        assert_eq!(standup, view);
+        Ok(())
+    }
+
+    #[test]
+    fn should_create_views_table() -> Result<()> {
+        let view = ViewConfig {
+            format: Some("short".to_string()),
+            ..ViewConfig::default()
+        };
+        let config = set_view("[style]\ntable = \"modern\"\n", "mine", &view)?;
+        assert!(config.contains("[views.mine]"));
+        assert!(!config.contains("[views]"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_prio_colors_in_theme() {
+        let err = toml::from_str::<Theme>("id = \"yellow\"\np1 = \"red\"").unwrap_err();
+        assert!(err.to_string().contains("unknown field `p1`"));
+    }
+}
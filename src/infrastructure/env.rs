@@ -1,16 +1,37 @@
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
+use std::fs;
 use std::path::PathBuf;
 
+use crate::domain::Selection;
 use crate::infrastructure::UserPaths;
 
-pub fn load_env(user_paths: &UserPaths) -> Result<()> {
-    let env_path = dotenv(user_paths);
-    if dotenv::from_filename(&env_path).is_err() {
-        return Err(anyhow!("✘ No .env file found at {env_path:?}"));
+pub fn dotenv(user_paths: &UserPaths) -> PathBuf {
+    user_paths.home.join(".todo/.env")
+}
+
+pub fn read_legacy_selection(user_paths: &UserPaths) -> Result<Option<Selection>> {
+    let path = dotenv(user_paths);
+    if !path.is_file() {
+        return Ok(None);
     }
-    Ok(())
+    let content = fs::read_to_string(&path).context("✘ Couldn't read .env")?;
+    let value = |key: &str| {
+        content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+            .map(|value| value.trim().trim_matches('"').to_string())
+            .filter(|value| !value.is_empty())
+    };
+    Ok(Some(Selection {
+        current: value("CURRENT"),
+        previous: value("PREVIOUS"),
+    }))
 }
 
-pub fn dotenv(user_paths: &UserPaths) -> PathBuf {
-    user_paths.home.join(".todo/.env")
+// Only once its selection is saved in the database, so that a failed import is retried.
+pub fn retire_legacy_selection(user_paths: &UserPaths) -> Result<()> {
+    let path = dotenv(user_paths);
+    let backup = path.with_extension("bak");
+    log::info!("moving {path:?} to {backup:?}");
+    fs::rename(&path, &backup).context("✘ Couldn't rename .env")
 }
@@ -2,7 +2,11 @@ pub mod config;
 pub mod editor;
 pub mod env;
 pub mod paths;
+pub mod picker;
+pub mod project;
 
 pub use config::*;
 pub use editor::*;
 pub use paths::*;
+pub use picker::*;
+pub use project::*;
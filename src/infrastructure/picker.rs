@@ -0,0 +1,33 @@
+use anyhow::{bail, Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::application::picker::Picker;
+
+pub struct TerminalPicker;
+
+impl Picker for TerminalPicker {
+    fn pick(&self, prompt: &str, options: &[String]) -> Result<Option<usize>> {
+        if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+            return Ok(None);
+        }
+        let width = options.len().to_string().len();
+        eprintln!("{prompt}");
+        for (idx, option) in options.iter().enumerate() {
+            eprintln!("  {:>width$}) {option}", idx + 1);
+        }
+        let mut stdin = io::stdin().lock();
+        loop {
+            eprint!("Select 1-{} (empty to abort): ", options.len());
+            io::stderr().flush()?;
+            let mut answer = String::new();
+            stdin
+                .read_line(&mut answer)
+                .context("✘ Couldn't read selection")?;
+            match answer.trim().parse::<usize>() {
+                _ if answer.trim().is_empty() => bail!("✘ Aborted"),
+                Ok(choice) if (1..=options.len()).contains(&choice) => return Ok(Some(choice - 1)),
+                _ => eprintln!("✘ Please enter a number between 1 and {}", options.len()),
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_FILE: &str = ".todo.toml";
+const DEFAULT_LIST: &str = "todo";
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub list: Option<String>,
+    // relative to the marker
+    pub database: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Project {
+    pub path: PathBuf,
+    pub config: ProjectConfig,
+}
+
+impl Project {
+    // a project database without a list uses `todo`, the list `todo init` creates
+    pub fn list(&self) -> Option<&str> {
+        match (&self.config.list, &self.config.database) {
+            (Some(list), _) => Some(list),
+            (None, Some(_)) => Some(DEFAULT_LIST),
+            (None, None) => None,
+        }
+    }
+
+    pub fn database(&self) -> Option<PathBuf> {
+        let database = self.config.database.as_ref()?;
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        Some(dir.join(database))
+    }
+}
+
+// The search ends at the root of the enclosing git repository, so that markers outside of it
+// don't leak in.
+pub fn find_project(start: &Path) -> Result<Option<Project>> {
+    for dir in start.ancestors() {
+        let path = dir.join(PROJECT_FILE);
+        if path.is_file() {
+            log::debug!("found project file at {path:?}");
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("✘ Couldn't read {}", path.display()))?;
+            let config = toml::from_str(&content)
+                .with_context(|| format!("✘ Couldn't parse {}", path.display()))?;
+            return Ok(Some(Project { path, config }));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("todo-project-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("repo/src/nested")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn should_find_marker_in_parent_directory() -> Result<()> {
+        let dir = temp_dir("parent");
+        fs::write(
+            dir.join("repo").join(PROJECT_FILE),
+            "list = \"backend\"\ndatabase = \"tasks.db\"\n",
+        )?;
+        let project = find_project(&dir.join("repo/src/nested"))?.unwrap();
+        assert_eq!(project.config.list.as_deref(), Some("backend"));
+        assert_eq!(project.database(), Some(dir.join("repo/tasks.db")));
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_stop_at_repository_root() -> Result<()> {
+        let dir = temp_dir("git");
+        fs::write(dir.join(PROJECT_FILE), "list = \"outer\"\n")?;
+        fs::create_dir(dir.join("repo/.git"))?;
+        assert_eq!(find_project(&dir.join("repo/src"))?, None);
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
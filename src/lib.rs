@@ -3,4 +3,5 @@ pub mod cli;
 pub mod domain;
 pub mod infrastructure;
 pub mod persistence;
+pub mod tui;
 pub mod util;
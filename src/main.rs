@@ -2,6 +2,7 @@ use clap::Parser;
 use std::process;
 
 use todo::application::config;
+use todo::application::output::Print;
 use todo::application::run::run;
 use todo::cli::app::Cli;
 use todo::cli::expand_alias;
@@ -9,17 +10,20 @@ use todo::cli::expand_alias;
 fn main() {
     env_logger::init();
     let args: Vec<String> = std::env::args().collect();
+    let output = Cli::output_format(&args);
     let config = match config::load_config() {
         Ok(config) => config,
         Err(err_config) => {
-            eprintln!("{:?}", err_config);
+            output.print_error(&err_config);
             process::exit(1);
         }
     };
     let expanded = expand_alias(args, &config);
     let cli = Cli::parse_from(expanded);
+    let output = cli.output;
+    cli.color.apply();
     if let Err(err) = run(cli, &config) {
-        eprintln!("{:?}", err);
+        output.print_error(&err);
         process::exit(1);
     }
 }
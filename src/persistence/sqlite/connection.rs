@@ -10,3 +10,15 @@ pub fn connect_to_db(db: &PathBuf) -> Result<Connection> {
         .context("✘ Couldn't set option 'foreign_keys' in database")?;
     Ok(conn)
 }
+
+// Joins the transaction in progress if there is one, so that several repository calls can
+// be made atomic as a whole.
+pub fn transaction<T>(conn: &Connection, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if !conn.is_autocommit() {
+        return f();
+    }
+    let tx = conn.unchecked_transaction()?;
+    let value = f()?;
+    tx.commit()?;
+    Ok(value)
+}
@@ -0,0 +1,124 @@
+use rusqlite::ToSql;
+
+use crate::domain::{
+    start_of_day, Comparison, Condition, DateField, Datetime, FilterExpr, Prio, Tag,
+};
+
+pub type Params = Vec<(String, Box<dyn ToSql>)>;
+
+// Conditions never evaluate to NULL, so that `not` matches exactly the remaining tasks.
+pub fn compile_filter(expr: &FilterExpr, params: &mut Params) -> String {
+    match expr {
+        FilterExpr::And(left, right) => format!(
+            "({} AND {})",
+            compile_filter(left, params),
+            compile_filter(right, params)
+        ),
+        FilterExpr::Or(left, right) => format!(
+            "({} OR {})",
+            compile_filter(left, params),
+            compile_filter(right, params)
+        ),
+        FilterExpr::Not(expr) => format!("NOT {}", compile_filter(expr, params)),
+        FilterExpr::Condition(condition) => compile_condition(condition, params),
+    }
+}
+
+fn compile_condition(condition: &Condition, params: &mut Params) -> String {
+    match condition {
+        Condition::Status(statuses) => {
+            let names: Vec<String> = statuses
+                .iter()
+                .map(|status| param(params, *status))
+                .collect();
+            format!("status IN ({})", names.join(", "))
+        }
+        Condition::Prio(Comparison::Equal, prios) => any(prios
+            .iter()
+            .map(|prio| format!("prio IS {}", param(params, *prio)))
+            .collect()),
+        Condition::Prio(comparison, prios) => {
+            let rank = param(params, prios.first().copied().unwrap_or(Prio::Empty));
+            format!(
+                "(prio IS NOT NULL AND prio {} {rank})",
+                operator(*comparison)
+            )
+        }
+        Condition::Tag(tags) => any(tags
+            .iter()
+            .map(|tag| match tag {
+                Tag(tag) if tag.is_empty() => "(tag IS NULL OR tag = '')".to_string(),
+                tag => format!("tag IS {}", param(params, tag.clone())),
+            })
+            .collect()),
+        Condition::Date(field, comparison, days) => {
+            let column = match field {
+                DateField::Due => "due",
+                DateField::Created => "COALESCE(created_at, 0)",
+                DateField::Updated => "COALESCE(last_updated, 0)",
+            };
+            any(days
+                .iter()
+                .map(|day| {
+                    let Some(day) = day else {
+                        return format!("due = {}", param(params, Datetime::epoch()));
+                    };
+                    let start = start_of_day(*day);
+                    let end = start_of_day(day.succ_opt().unwrap_or(*day));
+                    let range = match comparison {
+                        Comparison::Equal => format!(
+                            "{column} >= {} AND {column} < {}",
+                            param(params, start),
+                            param(params, end)
+                        ),
+                        Comparison::Less => format!("{column} < {}", param(params, start)),
+                        Comparison::LessOrEqual => format!("{column} < {}", param(params, end)),
+                        Comparison::Greater => format!("{column} >= {}", param(params, end)),
+                        Comparison::GreaterOrEqual => {
+                            format!("{column} >= {}", param(params, start))
+                        }
+                    };
+                    match field {
+                        // tasks without due date are neither before nor after any day
+                        DateField::Due => {
+                            format!("(due <> {} AND {range})", param(params, Datetime::epoch()))
+                        }
+                        _ => format!("({range})"),
+                    }
+                })
+                .collect())
+        }
+        Condition::Text(text) => {
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            let pattern = param(params, format!("%{escaped}%"));
+            format!("task LIKE {pattern} ESCAPE '\\'")
+        }
+    }
+}
+
+fn param(params: &mut Params, value: impl ToSql + 'static) -> String {
+    let name = format!(":where{}", params.len());
+    params.push((name.clone(), Box::new(value)));
+    name
+}
+
+fn operator(comparison: Comparison) -> &'static str {
+    match comparison {
+        Comparison::Equal => "=",
+        Comparison::Less => "<",
+        Comparison::LessOrEqual => "<=",
+        Comparison::Greater => ">",
+        Comparison::GreaterOrEqual => ">=",
+    }
+}
+
+// Matches any of the conditions
+fn any(conditions: Vec<String>) -> String {
+    match conditions.as_slice() {
+        [condition] => condition.clone(),
+        _ => format!("({})", conditions.join(" OR ")),
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+use crate::domain::PrioLevel;
+use crate::persistence::{create_state_table, SqlTodoListRepository};
+
+type Migration = fn(&Connection, &[String]) -> Result<()>;
+
+// Each migration receives the names of all item tables in the collection.
+// Schema migrations must be idempotent since freshly created tables already
+// carry the latest schema. A new collection starts at the latest version (see
+// `mark_latest`), so migrations rewriting values only ever see old data.
+const MIGRATIONS: &[Migration] = &[
+    add_external_ref,
+    add_item_numbers,
+    renumber_statuses,
+    clear_empty_prios,
+    add_state_table,
+    add_list_details,
+    add_last_numbers,
+];
+
+pub fn migrate(conn: &Connection) -> Result<()> {
+    if !table_exists(conn, SqlTodoListRepository::TABLE)? {
+        log::info!("no collection found, skipping migrations");
+        return Ok(());
+    }
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version >= MIGRATIONS.len() {
+        return Ok(());
+    }
+    let tables = list_tables(conn)?;
+    // a migration and its version bump commit together, so that none is ever run twice
+    for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        log::info!("running migration {}", idx + 1);
+        let tx = conn.unchecked_transaction()?;
+        migration(conn, &tables).context(format!("✘ Migration {} failed", idx + 1))?;
+        conn.pragma_update(None, "user_version", idx + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+fn add_external_ref(conn: &Connection, tables: &[String]) -> Result<()> {
+    for table in tables {
+        add_column_if_missing(conn, table, "external_ref", "TEXT")?;
+    }
+    Ok(())
+}
+
+// Numbers existing items per list in the order they were created.
+fn add_item_numbers(conn: &Connection, tables: &[String]) -> Result<()> {
+    for table in tables {
+        add_column_if_missing(conn, table, "number", "INTEGER")?;
+        if !table_exists(conn, table)? {
+            continue;
+        }
+        let sql =
+            format!("SELECT rowid FROM {table} WHERE number IS NULL ORDER BY created_at, rowid;");
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let sql = format!("SELECT COALESCE(MAX(number), 0) FROM {table};");
+        let max: i64 = conn.query_row(&sql, [], |row| row.get(0))?;
+        let sql = format!("UPDATE {table} SET number = ?1 WHERE rowid = ?2;");
+        for (offset, rowid) in rows.iter().enumerate() {
+            conn.execute(&sql, [max + 1 + offset as i64, *rowid])?;
+        }
+    }
+    Ok(())
+}
+
+// Open/closed used to be stored as 1/0, now statuses count up from open (0) to cancelled (4).
+fn renumber_statuses(conn: &Connection, tables: &[String]) -> Result<()> {
+    for table in tables {
+        if !table_exists(conn, table)? {
+            continue;
+        }
+        let sql = format!(
+            "UPDATE {table} SET status = CASE status WHEN 1 THEN 0 WHEN 0 THEN 3 ELSE status END;"
+        );
+        log::debug!("executing query `{}`", &sql);
+        conn.execute(&sql, [])?;
+    }
+    Ok(())
+}
+
+// "No priority" used to be stored as 0, which is now a valid rank (e.g. P0).
+fn clear_empty_prios(conn: &Connection, tables: &[String]) -> Result<()> {
+    for table in tables {
+        if !table_exists(conn, table)? {
+            continue;
+        }
+        let sql = format!("UPDATE {table} SET prio = NULL WHERE prio = 0;");
+        log::debug!("executing query `{}`", &sql);
+        conn.execute(&sql, [])?;
+    }
+    Ok(())
+}
+
+// The loaded list used to live in ~/.todo/.env, which is imported on first use.
+fn add_state_table(conn: &Connection, _tables: &[String]) -> Result<()> {
+    create_state_table(conn)
+}
+
+// Lists gained a description, a color and an archived flag.
+fn add_list_details(conn: &Connection, _tables: &[String]) -> Result<()> {
+    let table = SqlTodoListRepository::TABLE;
+    add_column_if_missing(conn, table, "description", "TEXT")?;
+    add_column_if_missing(conn, table, "color", "TEXT")?;
+    add_column_if_missing(conn, table, "archived", "INTEGER NOT NULL DEFAULT 0")
+}
+
+// Lists remember the highest item number they handed out, starting from their current items.
+fn add_last_numbers(conn: &Connection, _tables: &[String]) -> Result<()> {
+    let table = SqlTodoListRepository::TABLE;
+    add_column_if_missing(conn, table, "last_number", "INTEGER NOT NULL DEFAULT 0")
+}
+
+pub fn stale_prios(
+    conn: &Connection,
+    levels: &[PrioLevel],
+    lists: &[String],
+) -> Result<Vec<(String, i64)>> {
+    let mut stale = Vec::new();
+    for table in lists {
+        if !table_exists(conn, table)? {
+            continue;
+        }
+        let sql = format!("SELECT DISTINCT prio FROM {table} WHERE prio IS NOT NULL;");
+        let mut stmt = conn.prepare(&sql)?;
+        let ranks = stmt
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        stale.extend(
+            ranks
+                .into_iter()
+                .filter(|rank| !levels.iter().any(|level| level.rank == *rank))
+                .map(|rank| (table.clone(), rank)),
+        );
+    }
+    Ok(stale)
+}
+
+pub fn mark_latest(conn: &Connection) -> Result<()> {
+    conn.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    Ok(())
+}
+
+fn list_tables(conn: &Connection) -> Result<Vec<String>> {
+    let sql = format!("SELECT name FROM {}", SqlTodoListRepository::TABLE);
+    let mut stmt = conn.prepare(&sql)?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tables)
+}
+
+pub(crate) fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+pub fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    declaration: &str,
+) -> Result<()> {
+    if !table_exists(conn, table)? {
+        return Ok(());
+    }
+    let sql = format!("SELECT COUNT(*) FROM pragma_table_info('{table}') WHERE name = ?1");
+    let count: i64 = conn.query_row(&sql, [column], |row| row.get(0))?;
+    if count == 0 {
+        let sql = format!("ALTER TABLE {table} ADD COLUMN {column} {declaration};");
+        log::debug!("executing query `{}`", &sql);
+        conn.execute(&sql, [])?;
+    }
+    Ok(())
+}
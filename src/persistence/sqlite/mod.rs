@@ -1,8 +1,14 @@
 pub mod connection;
+pub mod filter;
+pub mod migrations;
 pub mod schema;
+pub mod state_repository;
 pub mod todo_item_repository;
 pub mod todo_list_repository;
 
 pub use connection::*;
+pub use filter::*;
+pub use migrations::*;
+pub use state_repository::*;
 pub use todo_item_repository::*;
 pub use todo_list_repository::*;
@@ -1,5 +1,3 @@
-use chrono::prelude::*;
-use chrono::Local;
 use rusqlite::{
     types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
     Result,
@@ -7,13 +5,15 @@ use rusqlite::{
 use std::fmt;
 
 use crate::domain::{Datetime, Prio, Status, Tag};
-use crate::infrastructure::{config, UserPaths};
 
 impl FromSql for Status {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
-            ValueRef::Integer(1) => Ok(Status::Open),
-            ValueRef::Integer(0) => Ok(Status::Closed),
+            ValueRef::Integer(0) => Ok(Status::Open),
+            ValueRef::Integer(1) => Ok(Status::InProgress),
+            ValueRef::Integer(2) => Ok(Status::Blocked),
+            ValueRef::Integer(3) => Ok(Status::Closed),
+            ValueRef::Integer(4) => Ok(Status::Cancelled),
             _ => Err(FromSqlError::InvalidType),
         }
     }
@@ -22,8 +22,11 @@ impl FromSql for Status {
 impl ToSql for Status {
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
         let value = match self {
-            Status::Open => 1,
-            Status::Closed => 0,
+            Status::Open => 0,
+            Status::InProgress => 1,
+            Status::Blocked => 2,
+            Status::Closed => 3,
+            Status::Cancelled => 4,
         };
         Ok(ToSqlOutput::from(value))
     }
@@ -33,7 +36,10 @@ impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Status::Open => write!(f, "✘"),
+            Status::InProgress => write!(f, "▶"),
+            Status::Blocked => write!(f, "⊘"),
             Status::Closed => write!(f, "✔"),
+            Status::Cancelled => write!(f, "–"),
         }
     }
 }
@@ -71,11 +77,8 @@ impl fmt::Display for Tag {
 impl FromSql for Prio {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         match value {
-            ValueRef::Integer(1) => Ok(Prio::P1),
-            ValueRef::Integer(2) => Ok(Prio::P2),
-            ValueRef::Integer(3) => Ok(Prio::P3),
-            ValueRef::Integer(99) => Ok(Prio::RND),
-            ValueRef::Integer(0) => Ok(Prio::Empty),
+            ValueRef::Integer(rank) => Ok(Prio(Some(rank))),
+            ValueRef::Null => Ok(Prio::Empty),
             _ => Err(FromSqlError::InvalidType),
         }
     }
@@ -83,14 +86,7 @@ impl FromSql for Prio {
 
 impl ToSql for Prio {
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        let value = match self {
-            Prio::P1 => 1,
-            Prio::P2 => 2,
-            Prio::P3 => 3,
-            Prio::RND => 99,
-            Prio::Empty => 0,
-        };
-        Ok(ToSqlOutput::from(value))
+        self.0.to_sql()
     }
 }
 
@@ -108,36 +104,3 @@ impl ToSql for Datetime {
         Ok(ToSqlOutput::from(self.timestamp))
     }
 }
-
-impl fmt::Display for Datetime {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let user_paths = UserPaths::new();
-        let config = config::read_config(&user_paths);
-        let due_date_format: &str = config
-            .as_ref()
-            .map(|c| c.style.due_date_display_format.as_str())
-            .unwrap_or("%x");
-        let today = Local::now().date_naive();
-        let next_mon = Datetime::next_weekday(Local::now(), Weekday::Mon);
-        let next_tue = Datetime::next_weekday(Local::now(), Weekday::Tue);
-        let next_wed = Datetime::next_weekday(Local::now(), Weekday::Wed);
-        let next_thu = Datetime::next_weekday(Local::now(), Weekday::Thu);
-        let next_fri = Datetime::next_weekday(Local::now(), Weekday::Fri);
-        let yesterday = today.pred_opt().unwrap(); // safe since epoch
-        let tomorrow = today.succ_opt().unwrap(); // safe until end of time
-        let date = DateTime::from_timestamp(self.timestamp, 0).unwrap();
-        let this = date.with_timezone(&Local).date_naive();
-        match this {
-            _ if *self == Datetime::epoch() => write!(f, ""),
-            dt if dt == yesterday => write!(f, "Yesterday"),
-            dt if dt == today => write!(f, "Today"),
-            dt if dt == tomorrow => write!(f, "Tomorrow"),
-            dt if dt == next_mon => write!(f, "Mon"),
-            dt if dt == next_tue => write!(f, "Tue"),
-            dt if dt == next_wed => write!(f, "Wed"),
-            dt if dt == next_thu => write!(f, "Thu"),
-            dt if dt == next_fri => write!(f, "EOW"),
-            _ => write!(f, "{}", date.naive_local().format(due_date_format)),
-        }
-    }
-}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use rusqlite::{named_params, Connection, OptionalExtension};
+
+use crate::domain::{Selection, SelectionRepository};
+use crate::persistence::connection::transaction;
+
+const CURRENT: &str = "current";
+const PREVIOUS: &str = "previous";
+
+pub struct SqlStateRepository<'conn> {
+    pub conn: &'conn Connection,
+}
+
+impl<'conn> SqlStateRepository<'conn> {
+    pub const TABLE: &'static str = "state";
+
+    pub fn new(conn: &'conn Connection) -> Self {
+        Self { conn }
+    }
+
+    fn fetch(&self, key: &str) -> Result<Option<String>> {
+        let sql = format!("SELECT value FROM {} WHERE key=:key;", Self::TABLE);
+        log::debug!("executing query `{}`", &sql);
+        let value = self
+            .conn
+            .query_row(&sql, named_params! {":key": key}, |row| row.get(0))
+            .optional()?;
+        Ok(value)
+    }
+
+    fn save(&self, key: &str, value: Option<&str>) -> Result<()> {
+        match value {
+            Some(value) => {
+                let sql = format!(
+                    "INSERT INTO {} (key, value) VALUES (:key, :value)
+                    ON CONFLICT(key) DO UPDATE SET value=excluded.value;",
+                    Self::TABLE
+                );
+                log::debug!("executing query `{}`", &sql);
+                self.conn
+                    .execute(&sql, named_params! {":key": key, ":value": value})?;
+            }
+            None => {
+                let sql = format!("DELETE FROM {} WHERE key=:key;", Self::TABLE);
+                log::debug!("executing query `{}`", &sql);
+                self.conn.execute(&sql, named_params! {":key": key})?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn create_state_table(conn: &Connection) -> Result<()> {
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+        SqlStateRepository::TABLE
+    );
+    conn.execute_batch(&sql)?;
+    Ok(())
+}
+
+impl SelectionRepository for SqlStateRepository<'_> {
+    fn fetch_selection(&self) -> Result<Selection> {
+        Ok(Selection {
+            current: self.fetch(CURRENT)?,
+            previous: self.fetch(PREVIOUS)?,
+        })
+    }
+
+    fn save_selection(&self, selection: &Selection) -> Result<()> {
+        transaction(self.conn, || {
+            self.save(CURRENT, selection.current.as_deref())?;
+            self.save(PREVIOUS, selection.previous.as_deref())
+        })
+    }
+}
@@ -3,11 +3,13 @@ use rusqlite::{named_params, Connection, OptionalExtension, ToSql};
 use thiserror::Error;
 
 use crate::domain::{
-    Datetime, ListFilters, Metadata, Prio, Status, StatusFilter, Tag, TodoItem, TodoItemCreate,
-    TodoItemDelete, TodoItemMetadata, TodoItemQuery, TodoItemQueryColumns, TodoItemRead,
-    TodoItemResolve, TodoItemSchema, TodoItemUpdate, TodoListRead,
+    AmbiguousIdError, Datetime, FilterExpr, ListFilters, Metadata, Prio, Status, StatusFilter, Tag,
+    TodoItem, TodoItemCreate, TodoItemDelete, TodoItemExternalRef, TodoItemMetadata, TodoItemQuery,
+    TodoItemQueryColumns, TodoItemRead, TodoItemResolve, TodoItemSchema, TodoItemTransfer,
+    TodoItemUpdate, TodoListRead, Transactional,
 };
-use crate::persistence::SqlTodoListRepository;
+use crate::persistence::connection::transaction;
+use crate::persistence::{compile_filter, SqlTodoListRepository};
 
 pub struct SqlTodoItemRepository<'conn> {
     pub conn: &'conn Connection,
@@ -31,6 +33,12 @@ pub enum ItemNotFoundError {
     InvalidId(String),
 }
 
+impl Transactional for SqlTodoItemRepository<'_> {
+    fn transaction<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        transaction(self.conn, f)
+    }
+}
+
 impl TodoItemSchema for SqlTodoItemRepository<'_> {
     fn create_table(&self, name: Option<&str>) -> Result<()> {
         let sql = format!(
@@ -45,7 +53,9 @@ prio INTEGER,
 due INTEGER,
 tag TEXT,
 created_at INTEGER,
-last_updated INTEGER
+last_updated INTEGER,
+external_ref TEXT,
+number INTEGER
 );",
             table = name.unwrap_or(&self.name),
             collection = SqlTodoListRepository::TABLE
@@ -59,27 +69,31 @@ last_updated INTEGER
 impl TodoItemCreate for SqlTodoItemRepository<'_> {
     fn add(&self, item: &TodoItem) -> Result<()> {
         let sql = format!(
-            "INSERT INTO {} (id, task, list_id, status, prio, due, tag, created_at, last_updated)
-VALUES (:id, :task, :list_id, :status, :prio, :due, :tag, :created_at, :last_updated);",
-            self.name
+            "INSERT INTO {table} (id, task, list_id, status, prio, due, tag, created_at, last_updated, number)
+VALUES (:id, :task, :list_id, :status, :prio, :due, :tag, :created_at, :last_updated, :number);",
+            table = self.name
         );
         let list_id = self.collection.fetch_id(&self.name)?;
-        log::debug!("executing query `{}`", &sql);
-        let _ = self.conn.execute(
-            &sql,
-            named_params! {
-                ":id": item.id,
-                ":task": item.task,
-                ":list_id": list_id,
-                ":status": item.status,
-                ":prio": item.prio,
-                ":due": item.due,
-                ":tag": item.tag,
-                ":created_at": Datetime::now(),
-                ":last_updated": Datetime::now(),
-            },
-        )?;
-        Ok(())
+        transaction(self.conn, || {
+            let number = self.collection.next_number(&self.name)?;
+            log::debug!("executing query `{}`", &sql);
+            let _ = self.conn.execute(
+                &sql,
+                named_params! {
+                    ":id": item.id,
+                    ":task": item.task,
+                    ":list_id": list_id,
+                    ":status": item.status,
+                    ":prio": item.prio,
+                    ":due": item.due,
+                    ":tag": item.tag,
+                    ":created_at": Datetime::now(),
+                    ":last_updated": Datetime::now(),
+                    ":number": number,
+                },
+            )?;
+            Ok(())
+        })
     }
 }
 
@@ -210,7 +224,11 @@ impl TodoItemUpdate for SqlTodoItemRepository<'_> {
         } else {
             self.fetch_list(ListFilters::default())?
         };
-        let ids: Vec<String> = tasks.iter().map(|item| item.id.clone()).collect();
+        let ids: Vec<String> = tasks
+            .iter()
+            .filter(|item| item.status != Status::Cancelled)
+            .map(|item| item.id.clone())
+            .collect();
         let sets = "last_updated=:last_updated, status=:status".to_string();
         let mut id_placeholders: Vec<String> = Vec::new();
         let mut params: Vec<(&str, &dyn ToSql)> = Vec::new();
@@ -250,9 +268,11 @@ impl TodoItemDelete for SqlTodoItemRepository<'_> {
 
     fn delete_all_items(&self) -> Result<()> {
         let sql = format!("DELETE FROM {}", self.name);
-        log::debug!("executing query `{}`", &sql);
-        self.conn.execute(&sql, [])?;
-        Ok(())
+        transaction(self.conn, || {
+            log::debug!("executing query `{}`", &sql);
+            self.conn.execute(&sql, [])?;
+            self.collection.reset_numbers(&self.name)
+        })
     }
 }
 
@@ -270,7 +290,7 @@ impl TodoItemQuery for SqlTodoItemRepository<'_> {
     }
 
     fn fetch_by_prio(&self, prio: Prio) -> Result<Vec<TodoItem>> {
-        let sql: String = format!("SELECT * FROM {} WHERE prio=:prio;", self.name);
+        let sql: String = format!("SELECT * FROM {} WHERE prio IS :prio;", self.name);
         let mut stmt = self.conn.prepare(&sql)?;
         let entries = stmt.query_map(named_params! {":prio": prio}, |row| {
             Ok(TodoItem {
@@ -386,19 +406,106 @@ impl TodoItemMetadata for SqlTodoItemRepository<'_> {
             let metadata = Metadata {
                 created_at: row.get::<_, Datetime>("created_at")?,
                 last_updated: row.get::<_, Datetime>("last_updated")?,
+                number: row.get::<_, Option<i64>>("number")?.unwrap_or_default(),
+                list: self.name.clone(),
             };
             Ok((item, metadata))
         })?;
         Ok((item, metadata))
     }
+
+    fn fetch_list_with_metadata(&self, filters: ListFilters) -> Result<Vec<(TodoItem, Metadata)>> {
+        let mut sql = format!("SELECT * FROM {}", self.name);
+        let mut params = Vec::new();
+        if let Some(filter_query) = parse_filters(filters) {
+            sql.push_str(" WHERE ");
+            sql.push_str(&filter_query.clause);
+            params = filter_query.params;
+        }
+        let query = NamedQuery {
+            clause: String::new(),
+            params,
+        };
+        log::debug!("executing query `{}`", &sql);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let entries = stmt
+            .query_map(query.named_params().as_slice(), |row| {
+                let item = TodoItem {
+                    id: row.get::<_, String>("id")?,
+                    task: row.get::<_, String>("task")?,
+                    status: row.get::<_, Status>("status")?,
+                    prio: row.get::<_, Prio>("prio")?,
+                    due: row.get::<_, Datetime>("due")?,
+                    tag: row.get::<_, Tag>("tag")?,
+                };
+                let metadata = Metadata {
+                    created_at: row.get::<_, Datetime>("created_at")?,
+                    last_updated: row.get::<_, Datetime>("last_updated")?,
+                    number: row.get::<_, Option<i64>>("number")?.unwrap_or_default(),
+                    list: self.name.clone(),
+                };
+                Ok((item, metadata))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+}
+
+impl TodoItemExternalRef for SqlTodoItemRepository<'_> {
+    fn fetch_id_by_external_ref(&self, external_ref: &str) -> Result<Option<String>> {
+        let sql = format!(
+            "SELECT id FROM {} WHERE external_ref=:external_ref;",
+            self.name
+        );
+        log::debug!("executing query `{}`", &sql);
+        let id = self
+            .conn
+            .query_row(&sql, named_params! {":external_ref": external_ref}, |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?;
+        Ok(id)
+    }
+
+    fn set_external_ref(&self, id: &str, external_ref: &str) -> Result<()> {
+        let id = self.resolve_id(id)?;
+        let sql = format!(
+            "UPDATE {} SET external_ref=:external_ref WHERE id=:id;",
+            self.name
+        );
+        log::debug!("executing query `{}`", &sql);
+        self.conn.execute(
+            &sql,
+            named_params! {":external_ref": external_ref, ":id": id},
+        )?;
+        Ok(())
+    }
 }
 
 impl TodoItemResolve for SqlTodoItemRepository<'_> {
     fn resolve_id(&self, id: &str) -> Result<String> {
-        let sql = format!("SELECT id FROM {} WHERE id LIKE :id || '%'", self.name);
+        let reference = match id.split_once(':') {
+            Some((list, reference)) if list == self.name => reference,
+            Some((list, _)) => {
+                return Err(anyhow!(
+                    "✘ '{id}' refers to list '{list}', but '{}' is loaded",
+                    self.name
+                ))
+            }
+            None => id,
+        };
+        if let Some(id) = self.resolve_number(reference)? {
+            return Ok(id);
+        }
+        let sql = format!(
+            "SELECT id FROM {} WHERE id LIKE :id || '%' ORDER BY id",
+            self.name
+        );
         let mut stmt = self.conn.prepare(&sql)?;
         let ids = stmt
-            .query_map(named_params! {":id": id}, |row| row.get::<_, String>(0))?
+            .query_map(named_params! {":id": reference}, |row| {
+                row.get::<_, String>(0)
+            })?
             .collect::<Result<Vec<_>, _>>()?;
         match ids.len() {
             0 => {
@@ -410,8 +517,71 @@ impl TodoItemResolve for SqlTodoItemRepository<'_> {
                 let id = ids[0].clone();
                 Ok(id)
             }
-            _ => Err(anyhow!("✘ Ambiguous prefix")),
+            _ => {
+                let candidates = ids
+                    .iter()
+                    .map(|id| self.fetch_item(id))
+                    .collect::<Result<Vec<_>>>()?;
+                Err(AmbiguousIdError {
+                    reference: id.to_string(),
+                    candidates,
+                }
+                .into())
+            }
+        }
+    }
+}
+
+impl TodoItemTransfer for SqlTodoItemRepository<'_> {
+    fn transfer(&self, target: &str, ids: &[(String, String)], remove: bool) -> Result<()> {
+        let list_id = self.collection.fetch_id(target)?;
+        let insert = format!(
+            "INSERT INTO {target} (id, list_id, task, status, prio, due, tag, created_at, last_updated, external_ref, number)
+SELECT :new_id, :list_id, task, status, prio, due, tag, created_at, last_updated, external_ref, :number
+FROM {source} WHERE id=:id;",
+            source = self.name
+        );
+        let delete = format!("DELETE FROM {} WHERE id=:id;", self.name);
+        transaction(self.conn, || {
+            for (id, new_id) in ids {
+                let number = self.collection.next_number(target)?;
+                log::debug!("executing query `{}`", &insert);
+                let copied = self.conn.execute(
+                    &insert,
+                    named_params! {":id": id, ":new_id": new_id, ":list_id": list_id, ":number": number},
+                )?;
+                if copied == 0 {
+                    return Err(ItemNotFoundError::InvalidId(id.clone()).into());
+                }
+                if remove {
+                    log::debug!("executing query `{}`", &delete);
+                    self.conn.execute(&delete, named_params! {":id": id})?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl SqlTodoItemRepository<'_> {
+    // Item numbers take precedence over id prefixes made up of digits only. A leading zero
+    // never starts a number, so ULID prefixes like `01` keep resolving as prefixes.
+    fn resolve_number(&self, reference: &str) -> Result<Option<String>> {
+        if reference.starts_with('0') || !reference.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(None);
         }
+        let Ok(number) = reference.parse::<i64>() else {
+            return Ok(None);
+        };
+        let sql = format!("SELECT id FROM {} WHERE number=:number;", self.name);
+        log::debug!("executing query `{}`", &sql);
+        let id = self
+            .conn
+            .query_row(&sql, named_params! {":number": number}, |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?;
+        Ok(id)
     }
 }
 
@@ -439,12 +609,19 @@ fn parse_filters(filters: ListFilters) -> Option<NamedQuery> {
     if let Some(status) = filters.status {
         match status {
             StatusFilter::All => {}
-            StatusFilter::Done => builder.add("status", 0),
-            StatusFilter::Do => builder.add("status", 1),
+            StatusFilter::Do => builder.add_any(
+                "status",
+                vec![Status::Open, Status::InProgress, Status::Blocked],
+            ),
+            StatusFilter::Done => builder.add("status", Status::Closed),
+            StatusFilter::Open => builder.add("status", Status::Open),
+            StatusFilter::InProgress => builder.add("status", Status::InProgress),
+            StatusFilter::Blocked => builder.add("status", Status::Blocked),
+            StatusFilter::Cancelled => builder.add("status", Status::Cancelled),
         }
     };
     if let Some(prio) = filters.prio {
-        builder.add("prio", prio);
+        builder.add_nullable("prio", prio);
     }
     if let Some(tag) = filters.tag {
         builder.add("tag", tag)
@@ -452,6 +629,9 @@ fn parse_filters(filters: ListFilters) -> Option<NamedQuery> {
     if let Some(due) = filters.due {
         builder.add("due", due);
     }
+    if let Some(expr) = filters.expr {
+        builder.add_filter(&expr);
+    }
     builder.build()
 }
 
@@ -474,6 +654,29 @@ impl ConditionsBuilder {
         self.params.push((param_name, Box::new(value)));
     }
 
+    // `IS` also matches NULL, e.g. tasks without priority
+    fn add_nullable<T: ToSql + 'static>(&mut self, column: &str, value: T) {
+        let param_name = format!(":{}", column);
+        self.conditions.push(format!("{column} IS {param_name}"));
+        self.params.push((param_name, Box::new(value)));
+    }
+
+    fn add_any<T: ToSql + 'static>(&mut self, column: &str, values: Vec<T>) {
+        let mut param_names = Vec::with_capacity(values.len());
+        for (idx, value) in values.into_iter().enumerate() {
+            let param_name = format!(":{column}{idx}");
+            param_names.push(param_name.clone());
+            self.params.push((param_name, Box::new(value)));
+        }
+        self.conditions
+            .push(format!("{column} IN ({})", param_names.join(", ")));
+    }
+
+    fn add_filter(&mut self, expr: &FilterExpr) {
+        let condition = compile_filter(expr, &mut self.params);
+        self.conditions.push(condition);
+    }
+
     fn build(self) -> Option<NamedQuery> {
         if self.conditions.is_empty() {
             None
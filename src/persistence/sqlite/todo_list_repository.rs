@@ -1,7 +1,12 @@
 use anyhow::Result;
 use rusqlite::{named_params, Connection};
 
-use crate::domain::{TodoListCreate, TodoListDelete, TodoListRead, TodoListSchema};
+use crate::domain::{
+    ListDetails, TodoListCreate, TodoListDelete, TodoListRead, TodoListSchema, TodoListUpdate,
+    Transactional,
+};
+use crate::persistence::connection::transaction;
+use crate::persistence::{create_state_table, mark_latest, table_exists};
 
 pub struct SqlTodoListRepository<'conn> {
     pub conn: &'conn Connection,
@@ -13,18 +18,61 @@ impl<'conn> SqlTodoListRepository<'conn> {
     pub fn new(conn: &'conn Connection) -> Self {
         Self { conn }
     }
+
+    // Numbers count up from the highest one the list ever handed out, so deleting its last item
+    // doesn't give that number to the next one. Items numbered before the mark was kept (or
+    // outside of it) still push it up.
+    pub fn next_number(&self, list_name: &str) -> Result<i64> {
+        let sql = format!(
+            "UPDATE {} SET last_number = MAX(last_number, (SELECT COALESCE(MAX(number), 0) FROM {list_name})) + 1
+WHERE name = (:name) RETURNING last_number;",
+            Self::TABLE
+        );
+        log::debug!("executing query `{}`", &sql);
+        self.conn
+            .query_row(&sql, named_params! { ":name": list_name }, |row| row.get(0))
+            .map_err(Into::into)
+    }
+
+    // Numbers are only handed out again once the items holding them are purged.
+    pub fn reset_numbers(&self, list_name: &str) -> Result<()> {
+        let sql = format!(
+            "UPDATE {} SET last_number = (SELECT COALESCE(MAX(number), 0) FROM {list_name})
+WHERE name = (:name);",
+            Self::TABLE
+        );
+        log::debug!("executing query `{}`", &sql);
+        self.conn
+            .execute(&sql, named_params! { ":name": list_name })?;
+        Ok(())
+    }
+}
+
+impl Transactional for SqlTodoListRepository<'_> {
+    fn transaction<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        transaction(self.conn, f)
+    }
 }
 
 impl TodoListSchema for SqlTodoListRepository<'_> {
     fn create_table(&self) -> Result<()> {
+        let is_new = !table_exists(self.conn, Self::TABLE)?;
         let sql = format!(
             "CREATE TABLE IF NOT EXISTS {} (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL UNIQUE
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            color TEXT,
+            archived INTEGER NOT NULL DEFAULT 0,
+            last_number INTEGER NOT NULL DEFAULT 0
         );",
             Self::TABLE
         );
         self.conn.execute_batch(&sql)?;
+        create_state_table(self.conn)?;
+        if is_new {
+            mark_latest(self.conn)?;
+        }
         Ok(())
     }
 }
@@ -57,6 +105,81 @@ impl TodoListRead for SqlTodoListRepository<'_> {
             .query_row(&sql, named_params! { ":name": list_name }, |row| row.get(0))
             .map_err(Into::into)
     }
+
+    fn fetch_details(&self) -> Result<Vec<ListDetails>> {
+        let sql = format!(
+            "SELECT name, description, color, archived FROM {}",
+            Self::TABLE
+        );
+        log::debug!("executing query {}", &sql);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(ListDetails {
+                    name: row.get(0)?,
+                    description: row.get(1)?,
+                    color: row.get(2)?,
+                    archived: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+impl TodoListUpdate for SqlTodoListRepository<'_> {
+    fn rename(&self, list_name: &str, new_name: &str) -> Result<()> {
+        let id = self.fetch_id(list_name)?;
+        transaction(self.conn, || {
+            if table_exists(self.conn, list_name)? {
+                let sql = format!("ALTER TABLE {list_name} RENAME TO {new_name};");
+                log::debug!("executing query `{}`", &sql);
+                self.conn.execute(&sql, [])?;
+            }
+            let sql = format!(
+                "UPDATE {} SET name = (:name) WHERE id = (:id);",
+                Self::TABLE
+            );
+            log::debug!("executing query `{}`", &sql);
+            self.conn
+                .execute(&sql, named_params! { ":name": new_name, ":id": id })?;
+            Ok(())
+        })
+    }
+
+    fn update_details(
+        &self,
+        list_name: &str,
+        description: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<()> {
+        let id = self.fetch_id(list_name)?;
+        let columns = [("description", description), ("color", color)];
+        for (column, value) in columns {
+            let Some(value) = value else { continue };
+            let value = Some(value).filter(|value| !value.is_empty());
+            let sql = format!(
+                "UPDATE {} SET {column} = (:value) WHERE id = (:id);",
+                Self::TABLE
+            );
+            log::debug!("executing query `{}`", &sql);
+            self.conn
+                .execute(&sql, named_params! { ":value": value, ":id": id })?;
+        }
+        Ok(())
+    }
+
+    fn set_archived(&self, list_name: &str, archived: bool) -> Result<()> {
+        let id = self.fetch_id(list_name)?;
+        let sql = format!(
+            "UPDATE {} SET archived = (:archived) WHERE id = (:id);",
+            Self::TABLE
+        );
+        log::debug!("executing query `{}`", &sql);
+        self.conn
+            .execute(&sql, named_params! { ":archived": archived, ":id": id })?;
+        Ok(())
+    }
 }
 
 impl TodoListDelete for SqlTodoListRepository<'_> {
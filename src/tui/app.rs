@@ -0,0 +1,409 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::str::FromStr;
+
+use crate::application::config::IdMode;
+use crate::domain::{
+    update::UpdateOptions, Datetime, Metadata, Prio, PrioLevel, Status, StatusFilter, Tag, TodoItem,
+};
+use crate::util::parse_task;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Prio,
+    Tag,
+    Due,
+}
+
+impl Field {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Prio => "prio",
+            Field::Tag => "tag",
+            Field::Due => "due",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Search,
+    Edit(Field),
+    Lists,
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Update(String, UpdateOptions),
+    SwitchList(String),
+    Reload,
+    Quit,
+}
+
+// Knows about entries, never about storage: key presses that change data turn into `Action`s,
+// which the event loop carries out through the use cases.
+pub struct App {
+    pub list: String,
+    pub lists: Vec<String>,
+    pub entries: Vec<(TodoItem, Metadata)>,
+    pub id_mode: IdMode,
+    pub date_input_format: String,
+    pub levels: Vec<PrioLevel>,
+    pub show_all: bool,
+    pub mode: Mode,
+    // index into the visible entries
+    pub selected: usize,
+    pub search: String,
+    pub input: String,
+    pub list_cursor: usize,
+    pub message: Option<String>,
+}
+
+impl App {
+    pub fn new(
+        list: &str,
+        lists: Vec<String>,
+        id_mode: IdMode,
+        date_input_format: &str,
+        levels: Vec<PrioLevel>,
+    ) -> Self {
+        Self {
+            list: list.to_string(),
+            lists,
+            entries: Vec::new(),
+            id_mode,
+            date_input_format: date_input_format.to_string(),
+            levels,
+            show_all: false,
+            mode: Mode::Normal,
+            selected: 0,
+            search: String::new(),
+            input: String::new(),
+            list_cursor: 0,
+            message: None,
+        }
+    }
+
+    pub fn status_filter(&self) -> StatusFilter {
+        if self.show_all {
+            StatusFilter::All
+        } else {
+            StatusFilter::Do
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<(TodoItem, Metadata)>) {
+        let selected_id = self.selected_item().map(|item| item.id.clone());
+        self.entries = entries;
+        self.selected = selected_id
+            .and_then(|id| self.visible().iter().position(|(item, _)| item.id == id))
+            .unwrap_or(self.selected);
+        self.clamp_selection();
+    }
+
+    pub fn visible(&self) -> Vec<&(TodoItem, Metadata)> {
+        let query = self.search.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|(item, _)| parse_task(&item.task).0.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn selected_item(&self) -> Option<&TodoItem> {
+        self.visible().get(self.selected).map(|(item, _)| item)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        match self.mode {
+            Mode::Normal => self.handle_normal(key.code),
+            Mode::Search => {
+                self.handle_search(key.code);
+                None
+            }
+            Mode::Edit(field) => self.handle_edit(field, key.code),
+            Mode::Lists => self.handle_lists(key.code),
+        }
+    }
+
+    fn handle_normal(&mut self, code: KeyCode) -> Option<Action> {
+        self.message = None;
+        match code {
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Esc if !self.search.is_empty() => {
+                self.search.clear();
+                self.clamp_selection();
+            }
+            KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('g') | KeyCode::Home => self.selected = 0,
+            KeyCode::Char('G') | KeyCode::End => {
+                self.selected = self.visible().len().saturating_sub(1)
+            }
+            KeyCode::Char('x') => return self.set_status(Status::Closed),
+            KeyCode::Char('o') => return self.set_status(Status::Open),
+            KeyCode::Char('p') => self.start_edit(Field::Prio),
+            KeyCode::Char('t') => self.start_edit(Field::Tag),
+            KeyCode::Char('d') => self.start_edit(Field::Due),
+            KeyCode::Char('/') => self.mode = Mode::Search,
+            KeyCode::Char('l') => {
+                self.list_cursor = self
+                    .lists
+                    .iter()
+                    .position(|list| *list == self.list)
+                    .unwrap_or_default();
+                self.mode = Mode::Lists;
+            }
+            KeyCode::Char('a') => {
+                self.show_all = !self.show_all;
+                return Some(Action::Reload);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_search(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.search.push(c),
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Enter => self.mode = Mode::Normal,
+            KeyCode::Esc => {
+                self.search.clear();
+                self.mode = Mode::Normal;
+            }
+            _ => return,
+        }
+        self.selected = 0;
+    }
+
+    fn handle_edit(&mut self, field: Field, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                let id = self.selected_item()?.id.clone();
+                return match self.parse_input(field) {
+                    Ok(options) => Some(Action::Update(id, options)),
+                    Err(err) => {
+                        self.message = Some(format!("✘ {err}"));
+                        None
+                    }
+                };
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_lists(&mut self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.list_cursor = (self.list_cursor + 1).min(self.lists.len().saturating_sub(1))
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.list_cursor = self.list_cursor.saturating_sub(1)
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.mode = Mode::Normal,
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                let list = self.lists.get(self.list_cursor)?.clone();
+                if list != self.list {
+                    return Some(Action::SwitchList(list));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub fn switch_list(&mut self, list: &str) {
+        self.list = list.to_string();
+        self.entries.clear();
+        self.search.clear();
+        self.selected = 0;
+    }
+
+    fn set_status(&self, status: Status) -> Option<Action> {
+        let item = self.selected_item()?;
+        let options = UpdateOptions {
+            due: None,
+            prio: None,
+            status: Some(status),
+            tag: None,
+        };
+        Some(Action::Update(item.id.clone(), options))
+    }
+
+    fn start_edit(&mut self, field: Field) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        self.input = match field {
+            Field::Prio => item.prio.name(&self.levels),
+            Field::Tag => item.tag.0.clone(),
+            // due dates are entered like on the command line, e.g. `fri` or `tomorrow`
+            Field::Due => String::new(),
+        };
+        self.mode = Mode::Edit(field);
+    }
+
+    // an empty input clears the field
+    fn parse_input(&self, field: Field) -> Result<UpdateOptions, String> {
+        let input = self.input.trim();
+        let mut options = UpdateOptions {
+            due: None,
+            prio: None,
+            status: None,
+            tag: None,
+        };
+        match field {
+            Field::Prio if input.is_empty() => options.prio = Some(Prio::Empty),
+            Field::Prio => {
+                options.prio =
+                    Some(Prio::parse_with(&self.levels, input).map_err(|err| err.to_string())?)
+            }
+            Field::Tag => options.tag = Some(Tag::from_str(input).map_err(|err| err.to_string())?),
+            Field::Due if input.is_empty() => options.due = Some(Datetime::epoch()),
+            Field::Due => {
+                options.due = Some(
+                    Datetime::parse(input, self.date_input_format.clone())
+                        .map_err(|err| err.to_string())?,
+                )
+            }
+        }
+        Ok(options)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        self.selected = self.selected.saturating_add_signed(delta);
+        self.clamp_selection();
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::todo::test_support::entry;
+
+    fn work(id: &str, task: &str) -> (TodoItem, Metadata) {
+        entry(id).task(task).prio(Prio::P2).tag("work").build()
+    }
+
+    fn app() -> App {
+        let mut app = App::new(
+            "todo",
+            vec!["todo".to_string(), "work".to_string()],
+            IdMode::Id,
+            "DMY",
+            PrioLevel::defaults(),
+        );
+        app.set_entries(vec![
+            work("a", "Buy milk"),
+            work("b", "Fix api\n\nthe body"),
+            work("c", "Write report"),
+        ]);
+        app
+    }
+
+    fn press(app: &mut App, keys: &str) -> Option<Action> {
+        keys.chars()
+            .map(|c| app.handle_key(KeyEvent::from(KeyCode::Char(c))))
+            .last()
+            .flatten()
+    }
+
+    fn enter(app: &mut App) -> Option<Action> {
+        app.handle_key(KeyEvent::from(KeyCode::Enter))
+    }
+
+    #[test]
+    fn should_move_selection_within_bounds() {
+        let mut app = app();
+        press(&mut app, "k");
+        assert_eq!(app.selected, 0);
+        press(&mut app, "jjjj");
+        assert_eq!(app.selected, 2);
+        press(&mut app, "g");
+        assert_eq!(app.selected_item().unwrap().id, "a");
+    }
+
+    #[test]
+    fn should_filter_incrementally_while_searching() {
+        let mut app = app();
+        press(&mut app, "/API");
+        assert_eq!(app.mode, Mode::Search);
+        assert_eq!(app.visible().len(), 1);
+        assert_eq!(app.selected_item().unwrap().id, "b");
+        enter(&mut app);
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.visible().len(), 1);
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(app.visible().len(), 3);
+    }
+
+    #[test]
+    fn should_turn_edits_into_updates_of_the_selected_item() {
+        let mut app = app();
+        press(&mut app, "j");
+        match press(&mut app, "x") {
+            Some(Action::Update(id, options)) => {
+                assert_eq!(id, "b");
+                assert_eq!(options.status, Some(Status::Closed));
+            }
+            action => panic!("unexpected action {action:?}"),
+        }
+        press(&mut app, "t");
+        assert_eq!(app.input, "work");
+        app.handle_key(KeyEvent::from(KeyCode::Backspace));
+        match enter(&mut app) {
+            Some(Action::Update(id, options)) => {
+                assert_eq!(id, "b");
+                assert_eq!(options.tag, Some(Tag("wor".to_string())));
+            }
+            action => panic!("unexpected action {action:?}"),
+        }
+    }
+
+    #[test]
+    fn should_report_invalid_input_without_updating() {
+        let mut app = app();
+        press(&mut app, "p");
+        press(&mut app, "9");
+        assert!(enter(&mut app).is_none());
+        assert!(app.message.unwrap().starts_with("✘ unknown priority"));
+    }
+
+    #[test]
+    fn should_keep_selection_on_reload() {
+        let mut app = app();
+        press(&mut app, "jj");
+        app.set_entries(vec![work("c", "Write report"), work("a", "Buy milk")]);
+        assert_eq!(app.selected_item().unwrap().id, "c");
+    }
+
+    #[test]
+    fn should_pick_another_list() {
+        let mut app = app();
+        press(&mut app, "l");
+        assert_eq!(app.mode, Mode::Lists);
+        press(&mut app, "j");
+        match enter(&mut app) {
+            Some(Action::SwitchList(list)) => assert_eq!(list, "work"),
+            action => panic!("unexpected action {action:?}"),
+        }
+    }
+}
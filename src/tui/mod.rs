@@ -0,0 +1,5 @@
+pub mod app;
+pub mod ui;
+
+pub use app::*;
+pub use ui::*;
@@ -0,0 +1,283 @@
+use colored::Color as AnsiColor;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+    },
+    Frame,
+};
+use std::str::FromStr;
+
+use crate::application::config::{IdMode, Theme};
+use crate::domain::{FormatContext, Status, TodoItem};
+use crate::tui::{App, Mode};
+use crate::util;
+
+const HINTS: &str =
+    "j/k move  x close  o open  p prio  t tag  d due  / search  l lists  a all  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, ctx: &FormatContext, theme: &Theme) {
+    let [body, status_line] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [list, detail] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+    draw_list(frame, list, app, ctx, theme);
+    draw_detail(frame, detail, app, ctx, theme);
+    draw_status_line(frame, status_line, app);
+    if app.mode == Mode::Lists {
+        draw_list_picker(frame, body, app);
+    }
+}
+
+fn draw_list(frame: &mut Frame, area: Rect, app: &App, ctx: &FormatContext, theme: &Theme) {
+    let visible = app.visible();
+    let rows = visible.iter().map(|(item, metadata)| {
+        let id = match app.id_mode {
+            IdMode::Id => ctx.id(&item.id),
+            IdMode::Number => metadata.number.to_string(),
+        };
+        let prio_color = ctx
+            .prio_level(item.prio)
+            .map(|level| color(&level.color))
+            .unwrap_or_default();
+        Row::new(vec![
+            Span::styled(item.status.to_string(), status_style(item.status, theme)),
+            Span::styled(id, Style::default().fg(color(&theme.id))),
+            Span::styled(ctx.prio(item.prio), Style::default().fg(prio_color)),
+            Span::raw(util::parse_task(&item.task).0),
+            Span::styled(ctx.due(&item.due), due_style(item, ctx, theme)),
+        ])
+    });
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Length(ctx.id_length.max(4) as u16),
+        Constraint::Length(4),
+        Constraint::Fill(1),
+        Constraint::Length(11),
+    ];
+    let title = if app.search.is_empty() {
+        format!(" {} ({}) ", app.list, visible.len())
+    } else {
+        format!(" {} ({}) /{} ", app.list, visible.len(), app.search)
+    };
+    let table = Table::new(rows, widths)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_detail(frame: &mut Frame, area: Rect, app: &App, ctx: &FormatContext, theme: &Theme) {
+    let block = Block::default().borders(Borders::ALL).title(" Details ");
+    let Some((item, metadata)) = app
+        .visible()
+        .get(app.selected)
+        .map(|(item, metadata)| (item, metadata))
+    else {
+        frame.render_widget(Paragraph::new("No tasks").block(block), area);
+        return;
+    };
+    let (title, body) = util::parse_task(&item.task);
+    let field = |name: &str, value: Span<'static>| {
+        Line::from(vec![
+            Span::styled(format!("{name:<9}"), Style::default().fg(Color::DarkGray)),
+            value,
+        ])
+    };
+    let mut lines = vec![
+        Line::styled(
+            util::prettify(&title, false),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Line::default(),
+        field("Id", Span::raw(item.id.clone())),
+        field("Number", Span::raw(metadata.number.to_string())),
+        field(
+            "Status",
+            Span::styled(item.status.name(), status_style(item.status, theme)),
+        ),
+        field("Prio", Span::raw(ctx.prio(item.prio))),
+        field("Tag", Span::raw(item.tag.0.clone())),
+        field(
+            "Due",
+            Span::styled(ctx.due(&item.due), due_style(item, ctx, theme)),
+        ),
+        field("Created", Span::raw(ctx.date(&metadata.created_at))),
+    ];
+    if !body.is_empty() {
+        lines.push(Line::default());
+        lines.extend(
+            util::prettify(&body, false)
+                .lines()
+                .map(|line| Line::raw(line.to_string())),
+        );
+    }
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_status_line(frame: &mut Frame, area: Rect, app: &App) {
+    let text = match app.mode {
+        Mode::Search => format!("/{}", app.search),
+        Mode::Edit(field) => format!("{}: {}", field.name(), app.input),
+        Mode::Lists => "j/k move  enter switch  esc cancel".to_string(),
+        Mode::Normal => app.message.clone().unwrap_or(HINTS.to_string()),
+    };
+    frame.render_widget(Paragraph::new(text), area);
+}
+
+fn draw_list_picker(frame: &mut Frame, area: Rect, app: &App) {
+    let width = app
+        .lists
+        .iter()
+        .map(|list| list.chars().count() as u16 + 4)
+        .max()
+        .unwrap_or_default()
+        .max(20)
+        .min(area.width);
+    let height = (app.lists.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let items = app.lists.iter().map(|list| {
+        let style = if *list == app.list {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        ListItem::new(Span::styled(list.clone(), style))
+    });
+    let picker = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" Lists "))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(app.list_cursor));
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(picker, popup, &mut state);
+}
+
+fn status_style(status: Status, theme: &Theme) -> Style {
+    let color_name = match status {
+        Status::Closed => &theme.done,
+        Status::InProgress => &theme.in_progress,
+        Status::Blocked => &theme.blocked,
+        Status::Cancelled => &theme.cancelled,
+        Status::Open => return Style::default(),
+    };
+    Style::default().fg(color(color_name))
+}
+
+fn due_style(item: &TodoItem, ctx: &FormatContext, theme: &Theme) -> Style {
+    if item.status.is_active() && ctx.is_overdue(&item.due) {
+        Style::default().fg(color(&theme.overdue))
+    } else {
+        Style::default()
+    }
+}
+
+fn color(name: &str) -> Color {
+    match AnsiColor::from_str(name) {
+        Ok(AnsiColor::Black) => Color::Black,
+        Ok(AnsiColor::Red) => Color::Red,
+        Ok(AnsiColor::Green) => Color::Green,
+        Ok(AnsiColor::Yellow) => Color::Yellow,
+        Ok(AnsiColor::Blue) => Color::Blue,
+        Ok(AnsiColor::Magenta) => Color::Magenta,
+        Ok(AnsiColor::Cyan) => Color::Cyan,
+        Ok(AnsiColor::White) => Color::Gray,
+        Ok(AnsiColor::BrightBlack) => Color::DarkGray,
+        Ok(AnsiColor::BrightRed) => Color::LightRed,
+        Ok(AnsiColor::BrightGreen) => Color::LightGreen,
+        Ok(AnsiColor::BrightYellow) => Color::LightYellow,
+        Ok(AnsiColor::BrightBlue) => Color::LightBlue,
+        Ok(AnsiColor::BrightMagenta) => Color::LightMagenta,
+        Ok(AnsiColor::BrightCyan) => Color::LightCyan,
+        Ok(AnsiColor::BrightWhite) => Color::White,
+        Ok(AnsiColor::TrueColor { r, g, b }) => Color::Rgb(r, g, b),
+        _ => Color::Reset,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Local, TimeZone};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use crate::domain::todo::test_support::entry;
+    use crate::domain::{Prio, PrioLevel};
+
+    fn render(app: &App) -> String {
+        let ctx = FormatContext::new(
+            "%x",
+            7,
+            Local.with_ymd_and_hms(2025, 6, 11, 12, 0, 0).unwrap(),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, app, &ctx, &Theme::default()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn app() -> App {
+        let mut app = App::new(
+            "todo",
+            vec!["todo".to_string(), "work".to_string()],
+            IdMode::Number,
+            "DMY",
+            PrioLevel::defaults(),
+        );
+        app.set_entries(vec![
+            entry("01JA")
+                .task("Buy milk")
+                .prio(Prio::P1)
+                .tag("work")
+                .number(1)
+                .build(),
+            entry("01JB")
+                .task("Fix api\n\n- [ ] write tests")
+                .prio(Prio::P1)
+                .tag("work")
+                .number(2)
+                .build(),
+        ]);
+        app
+    }
+
+    #[test]
+    fn should_render_list_and_details_of_selected_task() {
+        let mut app = app();
+        app.selected = 1;
+        let screen = render(&app);
+        assert!(screen.contains(" todo (2) "));
+        assert!(screen.contains("Buy milk"));
+        assert!(screen.contains("Id       01JB"));
+        assert!(screen.contains("□ write tests"));
+        assert!(screen.contains("j/k move"));
+    }
+
+    #[test]
+    fn should_render_search_and_list_picker() {
+        let mut app = app();
+        app.search = "milk".to_string();
+        app.mode = Mode::Lists;
+        let screen = render(&app);
+        assert!(screen.contains(" todo (1) /milk "));
+        assert!(!screen.contains("Fix api"));
+        assert!(screen.contains(" Lists "));
+        assert!(screen.contains("work"));
+    }
+}
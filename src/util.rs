@@ -1,4 +1,6 @@
+use colored::Colorize;
 use regex::{Captures, Regex};
+use std::sync::LazyLock;
 
 pub fn parse_task(task: &str) -> (String, String) {
     let (title, rest) = task.split_once('\n').unwrap_or((task, ""));
@@ -10,35 +12,51 @@ pub fn parse_task(task: &str) -> (String, String) {
     (title.to_string(), message)
 }
 
-pub fn prettify(haystack: &str) -> String {
+static RE_LINKS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(?P<text>[^\]]+)\]\((?P<url>[^)]+)\)").unwrap());
+static RE_BOX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s*\[\s\]\s*(?P<text>.+)$").unwrap());
+static RE_CHECKED_BOX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s*\[x\]\s*(?P<text>.+)$").unwrap());
+static RE_BULLETS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s+(?P<text>.+)$").unwrap());
+static RE_QUOTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^(?P<quote>[ \t]*)>\s*(?P<text>.+)$").unwrap());
+static RE_ITALIC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"_(?P<italic>[^_\n]+)_").unwrap());
+static RE_BOLD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*(?P<bold>[^*\n]+)\*").unwrap());
+
+// Without `color`, links print their url in parentheses and emphasis is dropped.
+pub fn prettify(haystack: &str, color: bool) -> String {
     // haystack = [text](link)
-    let re_links = Regex::new(r"\[(?P<text>[^\]]+)\]\((?P<url>[^)]+)\)").unwrap();
-    let re_box = Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s*\[\s\]\s*(?P<text>.+)$").unwrap();
-    let re_checked_box = Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s*\[x\]\s*(?P<text>.+)$").unwrap();
-    let re_bullets = Regex::new(r"(?m)^(?P<indent>[ \t]*)-\s+(?P<text>.+)$").unwrap();
-    let re_quote = Regex::new(r"(?m)^(?P<quote>[ \t]*)>\s*(?P<text>.+)$").unwrap();
-    let re_italic = Regex::new(r"_(?P<italic>[^_\n]+)_").unwrap();
-    let re_bold = Regex::new(r"\*(?P<bold>[^*\n]+)\*").unwrap();
-    let output = re_links.replace_all(haystack, |caps: &Captures| {
+    let output = RE_LINKS.replace_all(haystack, |caps: &Captures| {
         let text = &caps["text"];
         let url = &caps["url"];
-        link(text, url)
+        link(text, url, color)
+    });
+    let output = RE_CHECKED_BOX.replace_all(&output, "$indent ⏹ $text");
+    let output = RE_BOX.replace_all(&output, "$indent □ $text");
+    let output = RE_BULLETS.replace_all(&output, "$indent • $text");
+    let output = RE_QUOTE.replace_all(&output, "$indent ┃ $text");
+    let output = RE_ITALIC.replace_all(&output, |caps: &Captures| match color {
+        true => caps["italic"].italic().to_string(),
+        false => caps["italic"].to_string(),
+    });
+    let output = RE_BOLD.replace_all(&output, |caps: &Captures| match color {
+        true => caps["bold"].bold().to_string(),
+        false => caps["bold"].to_string(),
     });
-    let output = re_checked_box.replace_all(&output, "$indent ⏹ $text");
-    let output = re_box.replace_all(&output, "$indent □ $text");
-    let output = re_bullets.replace_all(&output, "$indent • $text");
-    let output = re_quote.replace_all(&output, "$indent ┃ $text");
-    let output = re_italic.replace_all(&output, "\x1b[3m$italic\x1b[0m");
-    let output = re_bold.replace_all(&output, "\x1b[1m$bold\x1b[0m");
     output.to_string()
 }
 
-pub fn link(text: &str, url: &str) -> String {
+pub fn link(text: &str, url: &str, color: bool) -> String {
     let url = if url.contains("://") {
         url
     } else {
         &format!("https://{}", url)
     };
+    if !color {
+        return format!("{} ({})", text, url);
+    }
     format!(
         "\u{1b}[34m↗\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\\u{1b}[0m",
         url, text,
@@ -75,4 +93,11 @@ mod unit_tests {
         assert_eq!(title, expected_title);
         assert_eq!(message, expected_message);
     }
+
+    #[test]
+    fn test_prettify_without_colors() {
+        let task = "Read *this* _now_: [docs](docs.rs)";
+        let expected = "Read this now: docs (https://docs.rs)";
+        assert_eq!(prettify(task, false), expected);
+    }
 }
@@ -15,7 +15,11 @@ impl MockSqlDb {
             r#"
             CREATE TABLE IF NOT EXISTS collection (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL
+                name TEXT NOT NULL,
+                description TEXT,
+                color TEXT,
+                archived INTEGER NOT NULL DEFAULT 0,
+                last_number INTEGER NOT NULL DEFAULT 0
             );
 
             CREATE TABLE IF NOT EXISTS todos (
@@ -29,7 +33,14 @@ impl MockSqlDb {
             due INTEGER,
             tag TEXT,
             created_at INTEGER,
-            last_updated INTEGER
+            last_updated INTEGER,
+            external_ref TEXT,
+            number INTEGER
+            );
+
+            CREATE TABLE IF NOT EXISTS state (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
             );
 
             INSERT INTO collection (name) VALUES ('todos');
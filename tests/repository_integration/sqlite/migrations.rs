@@ -0,0 +1,164 @@
+use anyhow::Result;
+use rusqlite::Connection;
+
+use todo::domain::{PrioLevel, TodoListSchema};
+use todo::persistence::{migrate, stale_prios, SqlTodoListRepository};
+
+fn columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("SELECT name FROM pragma_table_info('{table}')"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(columns)
+}
+
+#[test]
+fn migrate_legacy_tables() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE collection (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE
+        );
+        CREATE TABLE todos (
+            id TEXT PRIMARY KEY UNIQUE,
+            list_id INTEGER NOT NULL,
+            task TEXT NOT NULL,
+            status INTEGER DEFAULT 0,
+            prio INTEGER,
+            due INTEGER,
+            tag TEXT,
+            created_at INTEGER,
+            last_updated INTEGER
+        );
+        INSERT INTO collection (name) VALUES ('todos');
+        INSERT INTO todos (id, list_id, task, status, prio, created_at)
+            VALUES ('b', 1, 'second', 0, 0, 20);
+        INSERT INTO todos (id, list_id, task, status, prio, created_at)
+            VALUES ('a', 1, 'first', 1, 2, 10);
+        "#,
+    )?;
+
+    migrate(&conn)?;
+    assert!(columns(&conn, "todos")?.contains(&"external_ref".to_string()));
+    let numbers = conn
+        .prepare("SELECT id, number FROM todos ORDER BY number")?
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(numbers, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+    let statuses = conn
+        .prepare("SELECT status FROM todos ORDER BY number")?
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    // open (1) and closed (0) became 0 and 3
+    assert_eq!(statuses, vec![0, 3]);
+    let prios = conn
+        .prepare("SELECT prio FROM todos ORDER BY number")?
+        .query_map([], |row| row.get::<_, Option<i64>>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    // no priority (0) became NULL
+    assert_eq!(prios, vec![Some(2), None]);
+    assert_eq!(columns(&conn, "state")?, vec!["key", "value"]);
+    assert_eq!(
+        columns(&conn, "collection")?,
+        vec![
+            "id",
+            "name",
+            "description",
+            "color",
+            "archived",
+            "last_number"
+        ]
+    );
+
+    // running twice is a no-op
+    migrate(&conn)?;
+
+    Ok(())
+}
+
+#[test]
+fn rerun_failed_migration() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE collection (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE
+        );
+        CREATE TABLE todos (id TEXT PRIMARY KEY, status INTEGER, prio INTEGER, created_at INTEGER);
+        CREATE TABLE broken (id TEXT PRIMARY KEY, prio INTEGER, created_at INTEGER);
+        INSERT INTO collection (name) VALUES ('todos'), ('broken');
+        INSERT INTO todos (id, status, created_at) VALUES ('a', 1, 10), ('b', 0, 20);
+        "#,
+    )?;
+    let statuses = |conn: &Connection| -> Result<Vec<i64>> {
+        let statuses = conn
+            .prepare("SELECT status FROM todos ORDER BY created_at")?
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(statuses)
+    };
+
+    // renumbering the statuses of `broken` fails after `todos` has been renumbered
+    assert!(migrate(&conn).is_err());
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    assert_eq!(version, 2);
+    assert_eq!(statuses(&conn)?, vec![1, 0]);
+
+    conn.execute("ALTER TABLE broken ADD COLUMN status INTEGER", [])?;
+    migrate(&conn)?;
+    assert_eq!(statuses(&conn)?, vec![0, 3]);
+
+    Ok(())
+}
+
+#[test]
+fn skip_migrations_for_new_collection() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    SqlTodoListRepository::new(&conn).create_table()?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE todos (id TEXT PRIMARY KEY, status INTEGER);
+        INSERT INTO collection (name) VALUES ('todos');
+        INSERT INTO todos (id, status) VALUES ('a', 0);
+        "#,
+    )?;
+
+    migrate(&conn)?;
+    let status: i64 = conn.query_row("SELECT status FROM todos", [], |row| row.get(0))?;
+    assert_eq!(status, 0);
+
+    Ok(())
+}
+
+#[test]
+fn stale_prios_against_levels() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    SqlTodoListRepository::new(&conn).create_table()?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE todos (id TEXT PRIMARY KEY, prio INTEGER);
+        INSERT INTO collection (name) VALUES ('todos');
+        INSERT INTO todos (id, prio) VALUES ('a', 1), ('b', NULL), ('c', 7);
+        "#,
+    )?;
+
+    let lists = vec!["todos".to_string(), "missing".to_string()];
+    let stale = stale_prios(&conn, &PrioLevel::defaults(), &lists)?;
+    assert_eq!(stale, vec![("todos".to_string(), 7)]);
+    conn.execute("UPDATE todos SET prio = 3 WHERE id = 'c'", [])?;
+    assert!(stale_prios(&conn, &PrioLevel::defaults(), &lists)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn migrate_without_collection() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    migrate(&conn)?;
+    Ok(())
+}
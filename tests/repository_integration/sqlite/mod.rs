@@ -1,2 +1,4 @@
+pub mod migrations;
+pub mod state_repository;
 pub mod todo_item_repository;
 pub mod todo_list_repository;
@@ -0,0 +1,30 @@
+use anyhow::Result;
+
+use crate::mock::*;
+use todo::domain::{Selection, SelectionRepository};
+use todo::persistence::{create_state_table, SqlStateRepository};
+
+#[test]
+fn save_and_fetch_selection() -> Result<()> {
+    let db = MockSqlDb::new()?;
+    create_state_table(&db.conn)?;
+    let repo = SqlStateRepository::new(&db.conn);
+    assert_eq!(repo.fetch_selection()?, Selection::default());
+
+    let selection = Selection {
+        current: Some("work".to_string()),
+        previous: Some("todos".to_string()),
+    };
+    repo.save_selection(&selection)?;
+    assert_eq!(repo.fetch_selection()?, selection);
+
+    let selection = Selection {
+        current: Some("todos".to_string()),
+        previous: None,
+    };
+    repo.save_selection(&selection)?;
+    assert_eq!(repo.fetch_selection()?, selection);
+    assert_eq!(count_entries(&db.conn, "state")?, 1);
+
+    Ok(())
+}
@@ -3,11 +3,17 @@ use std::str::FromStr;
 use anyhow::Result;
 
 use crate::mock::*;
-use todo::domain::{Datetime, ListFilters, Prio, Status, StatusFilter, Tag};
+use todo::domain::import_items::ExternalItem;
 use todo::domain::{
-    TodoItemCreate, TodoItemDelete, TodoItemMetadata, TodoItemQuery, TodoItemQueryColumns,
-    TodoItemRead, TodoItemResolve, TodoItemUpdate,
+    AmbiguousIdError, Datetime, FilterContext, FilterExpr, ListFilters, Prio, PrioLevel, Status,
+    StatusFilter, Tag,
 };
+use todo::domain::{
+    TodoItemCreate, TodoItemDelete, TodoItemExternalRef, TodoItemMetadata, TodoItemQuery,
+    TodoItemQueryColumns, TodoItemRead, TodoItemResolve, TodoItemSchema, TodoItemTransfer,
+    TodoItemUpdate, TodoList, TodoListCreate, Transactional,
+};
+use todo::persistence::SqlTodoListRepository;
 
 /**************** TODO ITEM REPOSITORY *****************/
 
@@ -79,6 +85,7 @@ fn fetch_list() -> Result<()> {
         prio: None,
         due: None,
         tag: None,
+        expr: None,
     })?;
     assert_eq!(items.len(), 1);
     assert_eq!(items[0], mock_item_one.item);
@@ -88,6 +95,7 @@ fn fetch_list() -> Result<()> {
         prio: None,
         due: None,
         tag: None,
+        expr: None,
     })?;
     assert_eq!(items.len(), 1);
     assert_eq!(items[0], mock_item_two.item);
@@ -97,6 +105,7 @@ fn fetch_list() -> Result<()> {
         prio: Some(Prio::P1),
         due: None,
         tag: None,
+        expr: None,
     })?;
     assert_eq!(items.len(), 1);
     assert_eq!(items[0], mock_item_one.item);
@@ -161,12 +170,49 @@ fn fetch_by_tag() -> Result<()> {
             prio: Some(Prio::P1),
             due: None,
             tag: None,
+            expr: None,
         },
     )?;
     assert_eq!(response.len(), 1);
     Ok(())
 }
 
+#[test]
+fn fetch_list_with_metadata() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item_one = MockTodoItem::new(
+        "2a".to_string(),
+        "test-msg-1",
+        Some(Prio::P1),
+        None,
+        Some(Tag("test-tag".into())),
+    );
+    let mock_item_two = MockTodoItem::new(
+        "39".to_string(),
+        "test-msg-2",
+        None,
+        None,
+        Some(Tag("different-tag".into())),
+    );
+    let repo = mock_env.repo("todos");
+
+    repo.add(&mock_item_one.item)?;
+    repo.add(&mock_item_two.item)?;
+    let response = repo.fetch_list_with_metadata(ListFilters {
+        status: None,
+        prio: None,
+        due: None,
+        tag: Some(Tag("test-tag".into())),
+        expr: None,
+    })?;
+    assert_eq!(response.len(), 1);
+    let (item, metadata) = &response[0];
+    assert_eq!(item, &mock_item_one.item);
+    assert_ne!(metadata.created_at, Datetime::epoch());
+    assert_eq!(metadata.list, "todos");
+    Ok(())
+}
+
 #[test]
 fn fetch_task_by_id() -> Result<()> {
     let mock_env = MockItemEnv::new()?;
@@ -280,11 +326,11 @@ fn close_all() -> Result<()> {
     let repo = mock_env.repo("todos");
     repo.add(&mock_item_one.item)?;
     repo.add(&mock_item_two.item)?;
-    let count_initial = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count_initial = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count_initial, 2);
 
     repo.close_all(None)?;
-    let count = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count, 0);
 
     repo.update(
@@ -296,12 +342,151 @@ fn close_all() -> Result<()> {
     )?;
 
     repo.close_all(Some(Prio::P1))?;
-    let count = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count, 1);
 
     Ok(())
 }
 
+#[test]
+fn close_all_skips_cancelled() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item_one = MockTodoItem::new("2a".to_string(), "test-msg-1", None, None, None);
+    let mock_item_two = MockTodoItem::new("39".to_string(), "test-msg-2", None, None, None);
+    let repo = mock_env.repo("todos");
+    repo.add(&mock_item_one.item)?;
+    repo.add(&mock_item_two.item)?;
+    repo.update(
+        None,
+        None,
+        Some(Status::Cancelled),
+        None,
+        vec!["39".to_string()],
+    )?;
+
+    repo.close_all(None)?;
+
+    assert_eq!(repo.fetch_item("2a")?.status, Status::Closed);
+    assert_eq!(repo.fetch_item("39")?.status, Status::Cancelled);
+    Ok(())
+}
+
+#[test]
+fn fetch_list_by_status() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let repo = mock_env.repo("todos");
+    let statuses = [
+        ("1a", Status::Open),
+        ("2b", Status::InProgress),
+        ("3c", Status::Blocked),
+        ("4d", Status::Closed),
+        ("5e", Status::Cancelled),
+    ];
+    for (id, status) in statuses {
+        let mut mock_item = MockTodoItem::new(id.to_string(), "test-msg", None, None, None);
+        mock_item.item.status = status;
+        repo.add(&mock_item.item)?;
+    }
+    let ids = |status| -> Result<Vec<String>> {
+        let filters = ListFilters {
+            status: Some(status),
+            ..ListFilters::default()
+        };
+        Ok(repo
+            .fetch_list(filters)?
+            .into_iter()
+            .map(|item| item.id)
+            .collect())
+    };
+
+    assert_eq!(ids(StatusFilter::Do)?, vec!["1a", "2b", "3c"]);
+    assert_eq!(ids(StatusFilter::Done)?, vec!["4d"]);
+    assert_eq!(ids(StatusFilter::InProgress)?, vec!["2b"]);
+    assert_eq!(ids(StatusFilter::Cancelled)?, vec!["5e"]);
+    Ok(())
+}
+
+#[test]
+fn fetch_list_without_prio() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item_one =
+        MockTodoItem::new("2a".to_string(), "test-msg-1", Some(Prio::P1), None, None);
+    let mock_item_two = MockTodoItem::new("39".to_string(), "test-msg-2", None, None, None);
+    let repo = mock_env.repo("todos");
+    repo.add(&mock_item_one.item)?;
+    repo.add(&mock_item_two.item)?;
+
+    let filters = ListFilters {
+        prio: Some(Prio::Empty),
+        ..ListFilters::default()
+    };
+    let items = repo.fetch_list(filters)?;
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, "39");
+    Ok(())
+}
+
+#[test]
+fn fetch_list_where() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let repo = mock_env.repo("todos");
+    let today = Datetime::now();
+    let later = Datetime {
+        timestamp: today.timestamp + 10 * 24 * 3600,
+    };
+    let tag = |tag: &str| Some(Tag(tag.to_string()));
+    let items = [
+        ("a", "msg-1", Some(Prio::P1), today, tag("backend")),
+        (
+            "b",
+            "msg-2",
+            Some(Prio::P2),
+            Datetime::epoch(),
+            tag("infra"),
+        ),
+        ("c", "msg-3", None, later, tag("backend")),
+        ("d", "msg-4", Some(Prio::P1), today, tag("infra")),
+    ];
+    for (id, msg, prio, due, tag) in items {
+        repo.add(&MockTodoItem::new(id.to_string(), msg, prio, Some(due), tag).item)?;
+    }
+    repo.update(
+        None,
+        None,
+        Some(Status::Closed),
+        None,
+        vec!["d".to_string()],
+    )?;
+
+    let levels = PrioLevel::defaults();
+    let ctx = FilterContext {
+        today: today.to_local().date_naive(),
+        date_input_format: "DMY",
+        levels: &levels,
+    };
+    let ids = |expr: &str| -> Result<Vec<String>> {
+        let filters = ListFilters {
+            expr: Some(FilterExpr::parse(expr, &ctx)?),
+            ..ListFilters::default()
+        };
+        let items = repo.fetch_list(filters)?;
+        Ok(items.into_iter().map(|item| item.id).collect())
+    };
+    assert_eq!(
+        ids("prio:p1,p2 and (tag:backend or tag:infra) and not status:done")?,
+        vec!["a", "b"]
+    );
+    // tasks without due date are never before a day
+    assert_eq!(ids("due<=today")?, vec!["a", "d"]);
+    assert_eq!(ids("due:empty")?, vec!["b"]);
+    // tasks without priority are not p1 either
+    assert_eq!(ids("not prio:p1")?, vec!["b", "c"]);
+    assert_eq!(ids("text:MSG-3 or due>+5d")?, vec!["c"]);
+    assert_eq!(ids("created:today status:done")?, vec!["d"]);
+    Ok(())
+}
+
 #[test]
 fn update_batch() -> Result<()> {
     let mock_env = MockItemEnv::new()?;
@@ -322,7 +507,7 @@ fn update_batch() -> Result<()> {
     let repo = mock_env.repo("todos");
     repo.add(&mock_item_one.item)?;
     repo.add(&mock_item_two.item)?;
-    let count_initial = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count_initial = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count_initial, 2);
 
     repo.update(
@@ -332,11 +517,11 @@ fn update_batch() -> Result<()> {
         None,
         vec!["2a".to_string(), "39".to_string()],
     )?;
-    let count = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count, 0);
 
     repo.update(None, None, Some(Status::Open), None, vec!["2a".to_string()])?;
-    let count = count_entries_where("status = 1", &mock_env.db.conn)?;
+    let count = count_entries_where("status = 0", &mock_env.db.conn)?;
     assert_eq!(count, 1);
 
     repo.update(
@@ -375,9 +560,9 @@ fn update_conditions(
         _ => conditions.push("prio = 1".to_string()),
     };
     match status {
-        Some(Status::Open) => conditions.push("status = 1".to_string()),
-        Some(Status::Closed) => conditions.push("status = 0".to_string()),
-        _ => conditions.push("status = 1".to_string()),
+        Some(Status::Open) => conditions.push("status = 0".to_string()),
+        Some(Status::Closed) => conditions.push("status = 3".to_string()),
+        _ => conditions.push("status = 0".to_string()),
     };
     if let Some(tag) = tag {
         conditions.push(format!("tag = '{}'", tag.0));
@@ -528,3 +713,162 @@ fn resolve_ambiguous_id() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn resolve_number() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item_one = MockTodoItem::new("2a".to_string(), "test-msg-1", None, None, None);
+    let mock_item_two = MockTodoItem::new("39".to_string(), "test-msg-2", None, None, None);
+    let repo = mock_env.repo("todos");
+    repo.add(&mock_item_one.item)?;
+    repo.add(&mock_item_two.item)?;
+
+    assert_eq!(repo.resolve_id("2")?, "39");
+    assert_eq!(repo.resolve_id("todos:1")?, "2a");
+    // no item with number 3, so it resolves as an id prefix
+    assert_eq!(repo.resolve_id("3")?, "39");
+    assert!(repo.resolve_id("other:1").is_err());
+
+    // numbers of deleted items are not handed out while higher ones remain
+    repo.delete_item("1")?;
+    let mock_item_three = MockTodoItem::new("4b".to_string(), "test-msg-3", None, None, None);
+    repo.add(&mock_item_three.item)?;
+    let (_, metadata) = repo.fetch_item_and_metadata("4b")?;
+    assert_eq!(metadata.number, 3);
+
+    Ok(())
+}
+
+#[test]
+fn numbers_of_deleted_last_items_are_not_reused() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let repo = mock_env.repo("todos");
+    repo.add(&MockTodoItem::new("2a".to_string(), "test-msg-1", None, None, None).item)?;
+    repo.add(&MockTodoItem::new("39".to_string(), "test-msg-2", None, None, None).item)?;
+
+    repo.delete_item("2")?;
+    repo.add(&MockTodoItem::new("4b".to_string(), "test-msg-3", None, None, None).item)?;
+    assert_eq!(repo.fetch_item_and_metadata("4b")?.1.number, 3);
+
+    Ok(())
+}
+
+#[test]
+fn numbers_are_reused_after_purge() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let repo = mock_env.repo("todos");
+    repo.add(&MockTodoItem::new("2a".to_string(), "test-msg-1", None, None, None).item)?;
+    repo.add(&MockTodoItem::new("39".to_string(), "test-msg-2", None, None, None).item)?;
+
+    repo.delete_all_items()?;
+    repo.add(&MockTodoItem::new("4b".to_string(), "test-msg-3", None, None, None).item)?;
+    assert_eq!(repo.fetch_item_and_metadata("4b")?.1.number, 1);
+
+    Ok(())
+}
+
+#[test]
+fn resolve_ambiguous_prefix() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item_one = MockTodoItem::new("ab1".to_string(), "test-msg-1", None, None, None);
+    let mock_item_two = MockTodoItem::new("ab2".to_string(), "test-msg-2", None, None, None);
+    let repo = mock_env.repo("todos");
+    repo.add(&mock_item_one.item)?;
+    repo.add(&mock_item_two.item)?;
+
+    let err = repo.resolve_id("ab").unwrap_err();
+    let ambiguous = err
+        .downcast_ref::<AmbiguousIdError>()
+        .expect("expected an ambiguous id error");
+    let candidates: Vec<&str> = ambiguous
+        .candidates
+        .iter()
+        .map(|item| item.id.as_str())
+        .collect();
+    assert_eq!(candidates, vec!["ab1", "ab2"]);
+    assert_eq!(repo.resolve_id("ab2")?, "ab2");
+
+    Ok(())
+}
+
+#[test]
+fn external_ref() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let mock_item = MockTodoItem::default();
+    let repo = mock_env.repo("todos");
+    repo.add(&mock_item.item)?;
+    let url = "https://github.com/owner/repo/issues/1";
+
+    assert_eq!(repo.fetch_id_by_external_ref(url)?, None);
+    repo.set_external_ref("2a", url)?;
+    assert_eq!(repo.fetch_id_by_external_ref(url)?, Some("2a".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn transfer_items_between_lists() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let source = mock_env.repo("todos");
+    let target = mock_env.repo("work");
+    SqlTodoListRepository::new(&mock_env.db.conn).add("work")?;
+    target.create_table(None)?;
+    let mock_item = MockTodoItem::default();
+    source.add(&mock_item.item)?;
+    source.add(&MockTodoItem::new("3b".to_string(), "other", None, None, None).item)?;
+    let (_, metadata) = source.fetch_item_and_metadata("2a")?;
+
+    source.transfer("work", &[("2a".to_string(), "4c".to_string())], false)?;
+    let (copy, copy_metadata) = target.fetch_item_and_metadata("4c")?;
+    assert_eq!(copy.task, mock_item.item.task);
+    assert_eq!(copy.tag, mock_item.item.tag);
+    assert_eq!(copy_metadata.created_at, metadata.created_at);
+    assert_eq!(copy_metadata.number, 1);
+    assert!(source.fetch_item("2a").is_ok());
+
+    source.transfer("work", &[("3b".to_string(), "3b".to_string())], true)?;
+    assert_eq!(target.fetch_item_and_metadata("3b")?.1.number, 2);
+    assert!(source.fetch_item("3b").is_err());
+
+    // nothing is moved if one of the items is missing
+    let ids = [
+        ("2a".to_string(), "2a".to_string()),
+        ("ff".to_string(), "ff".to_string()),
+    ];
+    assert!(source.transfer("work", &ids, true).is_err());
+    assert!(source.fetch_item("2a").is_ok());
+    assert!(target.fetch_item("2a").is_err());
+    Ok(())
+}
+
+#[test]
+fn import_in_transaction() -> Result<()> {
+    let mock_env = MockItemEnv::new()?;
+    let repo = mock_env.repo("todos");
+    let item = |external_ref: &str| ExternalItem {
+        external_ref: external_ref.to_string(),
+        task: "issue".to_string(),
+        tag: Tag::default(),
+        due: Datetime::epoch(),
+        status: None,
+    };
+
+    // the second item fails, so the first one is rolled back along with its number
+    let mut counter = 0;
+    let items = vec![item("https://x/1"), item("")];
+    let result = repo.transaction(|| {
+        TodoList::new().import_items(&repo, items, || {
+            counter += 1;
+            format!("id-{counter}")
+        })
+    });
+    assert!(result.is_err());
+    assert_eq!(count_entries(&mock_env.db.conn, "todos")?, 0);
+    assert_eq!(repo.fetch_id_by_external_ref("https://x/1")?, None);
+
+    repo.transaction(|| {
+        TodoList::new().import_items(&repo, vec![item("https://x/1")], || "id-3".to_string())
+    })?;
+    assert_eq!(repo.fetch_item_and_metadata("id-3")?.1.number, 1);
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use crate::mock::*;
-use todo::domain::{TodoListCreate, TodoListDelete, TodoListRead};
+use todo::domain::{
+    ListDetails, Selection, SelectionRepository, TodoListCreate, TodoListDelete, TodoListRead,
+    TodoListUpdate, Transactional,
+};
+use todo::persistence::SqlStateRepository;
 
 #[test]
 fn add() -> Result<()> {
@@ -54,3 +58,91 @@ fn delete() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn rename() -> Result<()> {
+    let mock_env = MockListEnv::new()?;
+    let repo = mock_env.repo();
+
+    mock_env.db.conn.execute(
+        "INSERT INTO todos (id, list_id, task) VALUES ('a', 1, 'task');",
+        [],
+    )?;
+
+    repo.rename("todos", "renamed")?;
+    assert_eq!(repo.fetch_all()?, vec!["renamed"]);
+    // the items move along with the list
+    let count: i64 = count_entries(&mock_env.db.conn, "renamed")?;
+    assert_eq!(count, 1);
+    assert!(count_entries(&mock_env.db.conn, "todos").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn rename_in_transaction() -> Result<()> {
+    let mock_env = MockListEnv::new()?;
+    let repo = mock_env.repo();
+    let state_repo = SqlStateRepository::new(&mock_env.db.conn);
+    let selection = Selection {
+        current: Some("todos".to_string()),
+        previous: None,
+    };
+    state_repo.save_selection(&selection)?;
+
+    // a failure after the rename rolls back the rename and the selection saved along with it
+    let renamed = selection_of("renamed");
+    let result: Result<()> = repo.transaction(|| {
+        repo.rename("todos", "renamed")?;
+        state_repo.save_selection(&renamed)?;
+        bail!("Fake error after renaming")
+    });
+    assert!(result.is_err());
+    assert_eq!(repo.fetch_all()?, vec!["todos"]);
+    assert_eq!(count_entries(&mock_env.db.conn, "todos")?, 0);
+    assert_eq!(state_repo.fetch_selection()?, selection);
+
+    repo.transaction(|| {
+        repo.rename("todos", "renamed")?;
+        state_repo.save_selection(&renamed)
+    })?;
+    assert_eq!(repo.fetch_all()?, vec!["renamed"]);
+    assert_eq!(state_repo.fetch_selection()?, renamed);
+
+    Ok(())
+}
+
+#[test]
+fn update_details() -> Result<()> {
+    let mock_env = MockListEnv::new()?;
+    let repo = mock_env.repo();
+
+    repo.update_details("todos", Some("Chores"), Some("green"))?;
+    repo.set_archived("todos", true)?;
+    assert_eq!(
+        repo.fetch_details()?,
+        vec![ListDetails {
+            name: "todos".to_string(),
+            description: Some("Chores".to_string()),
+            color: Some("green".to_string()),
+            archived: true,
+        }]
+    );
+
+    // an empty value clears the field, a missing one keeps it
+    repo.update_details("todos", None, Some(""))?;
+    repo.set_archived("todos", false)?;
+    let details = repo.fetch_details()?.remove(0);
+    assert_eq!(details.description.as_deref(), Some("Chores"));
+    assert_eq!(details.color, None);
+    assert!(!details.archived);
+
+    Ok(())
+}
+
+fn selection_of(current: &str) -> Selection {
+    Selection {
+        current: Some(current.to_string()),
+        previous: None,
+    }
+}